@@ -0,0 +1,60 @@
+use aoc_common::{AnySolution, Generator, Image, Input, Render, Result, Rng};
+
+pub type Day = dyn AnySolution;
+
+pub const DAYS: &[&Day] = &[
+    &day1::Day1,
+    &day2::Day2,
+    &day3::Day3,
+    &day4::Day4,
+    &day5::Day5,
+    &day6::Day6,
+    // new-day: days
+];
+
+pub fn find_day(number: u32) -> Option<&'static Day> {
+    DAYS.iter().copied().find(|d| d.day() == number)
+}
+
+/// Makes up input for a day from a seed and a size, see [`Generator`].
+pub type GenerateFn = fn(u64, usize) -> String;
+
+fn generate<S: Generator>(seed: u64, size: usize) -> String {
+    S::generate(&mut Rng::new(seed), size, &Default::default())
+}
+
+pub const GENERATORS: &[(u32, GenerateFn)] = &[
+    (1, generate::<day1::Day1>),
+    (2, generate::<day2::Day2>),
+    (3, generate::<day3::Day3>),
+    (4, generate::<day4::Day4>),
+    (5, generate::<day5::Day5>),
+    (6, generate::<day6::Day6>),
+];
+
+pub fn find_generator(number: u32) -> Option<GenerateFn> {
+    GENERATORS
+        .iter()
+        .find(|(day, _)| *day == number)
+        .map(|&(_, generate)| generate)
+}
+
+/// Draws a part of a day on an input, see [`Render`].
+pub type RenderFn = fn(&Input, u32) -> Result<Image>;
+
+fn render<S: Render>(input: &Input, part: u32) -> Result<Image> {
+    S::render(&S::parse(input)?, part)
+}
+
+pub const RENDERERS: &[(u32, RenderFn)] = &[
+    (3, render::<day3::Day3>),
+    (4, render::<day4::Day4>),
+    (6, render::<day6::Day6>),
+];
+
+pub fn find_renderer(number: u32) -> Option<RenderFn> {
+    RENDERERS
+        .iter()
+        .find(|(day, _)| *day == number)
+        .map(|&(_, render)| render)
+}
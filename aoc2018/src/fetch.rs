@@ -0,0 +1,333 @@
+//! Downloading puzzle inputs from the Advent of Code site into the days'
+//! `data` directories.
+use std::env;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::thread;
+use std::time::Duration;
+
+use aoc_common::Result;
+
+const YEAR: u32 = 2018;
+const DEFAULT_URL: &str = "https://adventofcode.com";
+const DEFAULT_NAME: &str = "input";
+const USER_AGENT: &str = concat!("aoc2018/", env!("CARGO_PKG_VERSION"), " input fetcher");
+
+/// Environment variable with the path of the config file.
+pub const CONFIG_VAR: &str = "AOC_CONFIG";
+
+/// Times a rate limited download is retried.
+const RETRIES: u32 = 3;
+/// Longest wait before a retry, in seconds, whatever the server asks for.
+const MAX_RETRY_AFTER: u64 = 60;
+
+/// Settings from the config file: `key = value` lines, where blank lines and
+/// lines starting with `#` are ignored.
+///
+/// `session` is the value of the session cookie of a logged in browser.
+/// `url` replaces the address of the Advent of Code site, for testing.
+/// `name` names the inputs of the account, `dayN/data/NAME.txt`, so that
+/// teammates sharing a checkout each keep their own. It defaults to `input`,
+/// the bundled inputs.
+#[derive(Debug)]
+pub struct Config {
+    pub session: String,
+    pub url: String,
+    pub name: String,
+}
+
+impl Config {
+    pub fn load(path: &Path) -> Result<Config> {
+        let text = fs::read_to_string(path).map_err(|e| {
+            format!(
+                "Unable to read config file '{}': {}. It needs a 'session = TOKEN' line",
+                path.display(),
+                e
+            )
+        })?;
+        Config::parse(&text)
+            .map_err(|e| format!("Invalid config file '{}': {}", path.display(), e).into())
+    }
+
+    fn parse(text: &str) -> std::result::Result<Config, String> {
+        let mut session = None;
+        let mut url = DEFAULT_URL.to_string();
+        let mut name = DEFAULT_NAME.to_string();
+        for (index, line) in text.lines().enumerate() {
+            let line = line.trim();
+            if line.is_empty() || line.starts_with('#') {
+                continue;
+            }
+            let (key, value) = match line.find('=') {
+                Some(pos) => (line[..pos].trim(), line[pos + 1..].trim()),
+                None => return Err(format!("line {}: expected 'key = value'", index + 1)),
+            };
+            match key {
+                "session" => session = Some(value.to_string()),
+                "url" => url = value.trim_end_matches('/').to_string(),
+                "name" if valid_name(value) => name = value.to_string(),
+                "name" => {
+                    return Err(format!(
+                        "line {}: the name '{}' is not a plain file name",
+                        index + 1,
+                        value
+                    ))
+                }
+                _ => return Err(format!("line {}: unknown key '{}'", index + 1, key)),
+            }
+        }
+        let session = session.ok_or("no session token, add a 'session = TOKEN' line")?;
+        Ok(Config { session, url, name })
+    }
+
+    /// Where the input of `day` goes, in the workspace at `root`. The path
+    /// only depends on the day number, so that an input can be fetched
+    /// before the day's crate exists.
+    pub fn input_path(&self, root: &Path, day: u32) -> PathBuf {
+        root.join(format!("day{}/data/{}.txt", day, self.name))
+    }
+}
+
+fn valid_name(name: &str) -> bool {
+    let allowed = |c: char| c.is_ascii_alphanumeric() || c == '-' || c == '_';
+    !name.is_empty() && name.chars().all(allowed)
+}
+
+/// The config file: `$AOC_CONFIG`, or `~/.config/aoc2018/config`.
+pub fn default_config_path() -> Option<PathBuf> {
+    default_config_path_from(env::var_os(CONFIG_VAR), env::var_os("HOME"))
+}
+
+fn default_config_path_from(
+    var: Option<std::ffi::OsString>,
+    home: Option<std::ffi::OsString>,
+) -> Option<PathBuf> {
+    var.map(PathBuf::from)
+        .or_else(|| home.map(|home| Path::new(&home).join(".config/aoc2018/config")))
+}
+
+#[derive(Debug, PartialEq)]
+pub enum Fetched {
+    /// The input was already there, nothing was downloaded.
+    Cached,
+    Downloaded,
+}
+
+/// Download the input of `day` to `dest`, unless it is there already.
+pub fn fetch(config: &Config, day: u32, dest: &Path) -> Result<Fetched> {
+    if dest.exists() {
+        return Ok(Fetched::Cached);
+    }
+    let text = download(config, day)?;
+    if let Some(dir) = dest.parent() {
+        fs::create_dir_all(dir)?;
+    }
+    // never leave a partial input behind, it would count as cached
+    let partial = dest.with_extension("partial");
+    fs::write(&partial, text)?;
+    fs::rename(&partial, dest)?;
+    Ok(Fetched::Downloaded)
+}
+
+fn download(config: &Config, day: u32) -> Result<String> {
+    let url = format!("{}/{}/day/{}/input", config.url, YEAR, day);
+    let agent = ureq::AgentBuilder::new()
+        .user_agent(USER_AGENT)
+        .timeout(Duration::from_secs(30))
+        .build();
+    let cookie = format!("session={}", config.session);
+    let mut retries = 0;
+    loop {
+        match agent.get(&url).set("Cookie", &cookie).call() {
+            Ok(response) => return Ok(response.into_string()?),
+            Err(ureq::Error::Status(429, response)) if retries < RETRIES => {
+                let wait = response
+                    .header("Retry-After")
+                    .and_then(|s| s.parse().ok())
+                    .unwrap_or(1);
+                thread::sleep(Duration::from_secs(wait.min(MAX_RETRY_AFTER)));
+                retries += 1;
+            }
+            Err(ureq::Error::Status(status, _)) => return Err(status_error(day, status).into()),
+            Err(e) => return Err(format!("Unable to download '{}': {}", url, e).into()),
+        }
+    }
+}
+
+fn status_error(day: u32, status: u16) -> String {
+    match status {
+        400 | 401 | 403 => format!(
+            "The session token was rejected (HTTP {}), it has probably expired",
+            status
+        ),
+        404 => format!("The input of day {} is not available yet (HTTP 404)", day),
+        429 => "Still rate limited after retrying (HTTP 429), try again later".to_string(),
+        _ => format!(
+            "Unable to download the input of day {}: HTTP {}",
+            day, status
+        ),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::io::{BufRead, BufReader, Write};
+    use std::net::TcpListener;
+
+    /// Stand-in for the site: answers one connection per response, in order,
+    /// and returns the request lines and cookies it got.
+    fn serve(responses: Vec<String>) -> (Config, thread::JoinHandle<Vec<String>>) {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let url = format!("http://{}", listener.local_addr().unwrap());
+        let server = thread::spawn(move || {
+            let mut requests = Vec::new();
+            for response in responses {
+                let (mut stream, _) = listener.accept().unwrap();
+                let mut reader = BufReader::new(stream.try_clone().unwrap());
+                loop {
+                    let mut line = String::new();
+                    reader.read_line(&mut line).unwrap();
+                    let line = line.trim_end();
+                    if line.is_empty() {
+                        break;
+                    }
+                    if line.starts_with("GET") || line.to_lowercase().starts_with("cookie") {
+                        requests.push(line.to_string());
+                    }
+                }
+                stream.write_all(response.as_bytes()).unwrap();
+            }
+            requests
+        });
+        let config = Config {
+            session: "53cr3t".to_string(),
+            url,
+            name: DEFAULT_NAME.to_string(),
+        };
+        (config, server)
+    }
+
+    fn response(status: &str, headers: &str, body: &str) -> String {
+        format!(
+            "HTTP/1.1 {}\r\n{}Content-Length: {}\r\nConnection: close\r\n\r\n{}",
+            status,
+            headers,
+            body.len(),
+            body
+        )
+    }
+
+    fn scratch_file(name: &str) -> PathBuf {
+        let dir = env::temp_dir().join(format!("aoc2018-fetch-{}-{}", std::process::id(), name));
+        let _ = fs::remove_dir_all(&dir);
+        dir.join("data/input.txt")
+    }
+
+    #[test]
+    fn downloads_once() {
+        let (config, server) = serve(vec![response("200 OK", "", "+1\n-2\n")]);
+        let dest = scratch_file("once");
+        assert_eq!(fetch(&config, 1, &dest).unwrap(), Fetched::Downloaded);
+        assert_eq!(fs::read_to_string(&dest).unwrap(), "+1\n-2\n");
+        assert_eq!(
+            server.join().unwrap(),
+            vec!["GET /2018/day/1/input HTTP/1.1", "Cookie: session=53cr3t"]
+        );
+
+        // the server is gone, so this would fail if it tried to download
+        assert_eq!(fetch(&config, 1, &dest).unwrap(), Fetched::Cached);
+        assert_eq!(fs::read_to_string(&dest).unwrap(), "+1\n-2\n");
+    }
+
+    #[test]
+    fn retries_when_rate_limited() {
+        let limited = response("429 Too Many Requests", "Retry-After: 0\r\n", "");
+        let (config, server) = serve(vec![
+            limited.clone(),
+            limited,
+            response("200 OK", "", "abcdef\n"),
+        ]);
+        let dest = scratch_file("rate");
+        assert_eq!(fetch(&config, 2, &dest).unwrap(), Fetched::Downloaded);
+        assert_eq!(server.join().unwrap().len(), 6);
+    }
+
+    #[test]
+    fn gives_up_when_rate_limited() {
+        let limited = response("429 Too Many Requests", "Retry-After: 0\r\n", "");
+        let (config, server) = serve(vec![limited; RETRIES as usize + 1]);
+        let dest = scratch_file("limited");
+        let err = fetch(&config, 2, &dest).unwrap_err();
+        assert!(err.to_string().contains("rate limited"), "{}", err);
+        assert!(!dest.exists());
+        server.join().unwrap();
+    }
+
+    #[test]
+    fn rejected_session() {
+        let body = "Puzzle inputs differ by user.  Please log in to get your puzzle input.";
+        let (config, server) = serve(vec![response("400 Bad Request", "", body)]);
+        let dest = scratch_file("auth");
+        let err = fetch(&config, 3, &dest).unwrap_err();
+        assert!(
+            err.to_string().contains("session token was rejected"),
+            "{}",
+            err
+        );
+        assert!(!dest.exists());
+        server.join().unwrap();
+    }
+
+    #[test]
+    fn not_unlocked() {
+        let (config, server) = serve(vec![response("404 Not Found", "", "Not Found")]);
+        let dest = scratch_file("locked");
+        let err = fetch(&config, 25, &dest).unwrap_err();
+        assert!(
+            err.to_string().contains("day 25 is not available"),
+            "{}",
+            err
+        );
+        server.join().unwrap();
+    }
+
+    #[test]
+    fn config_file() {
+        let config = Config::parse("# AoC\nsession = abc123\nurl = http://localhost:8000/\n");
+        let config = config.unwrap();
+        assert_eq!(config.session, "abc123");
+        assert_eq!(config.url, "http://localhost:8000");
+        assert_eq!(Config::parse("session=x").unwrap().url, DEFAULT_URL);
+        assert_eq!(Config::parse("session=x").unwrap().name, "input");
+        assert_eq!(Config::parse("session=x\nname=ann").unwrap().name, "ann");
+        assert!(Config::parse("session=x\nname=../ann").is_err());
+        assert!(Config::parse("session=x\nname=").is_err());
+        assert!(Config::parse("url = http://x\n").is_err());
+        assert!(Config::parse("token = x\n").is_err());
+    }
+
+    #[test]
+    fn input_per_account() {
+        let config = |name: &str| Config {
+            session: "x".to_string(),
+            url: DEFAULT_URL.to_string(),
+            name: name.to_string(),
+        };
+        let root = Path::new("/aoc");
+        let path = config("input").input_path(root, 1);
+        assert_eq!(path, Path::new("/aoc/day1/data/input.txt"));
+        // a day without a crate yet
+        let path = config("ann").input_path(root, 25);
+        assert_eq!(path, Path::new("/aoc/day25/data/ann.txt"));
+    }
+
+    #[test]
+    fn config_path() {
+        let path = default_config_path_from(Some("my.conf".into()), Some("/home/me".into()));
+        assert_eq!(path.unwrap(), Path::new("my.conf"));
+        let path = default_config_path_from(None, Some("/home/me".into()));
+        assert_eq!(path.unwrap(), Path::new("/home/me/.config/aoc2018/config"));
+        assert_eq!(default_config_path_from(None, None), None);
+    }
+}
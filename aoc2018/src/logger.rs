@@ -0,0 +1,57 @@
+//! Diagnostics from the solvers and the runner, on stderr so that they never
+//! mix with the table or JSON output.
+use log::{LevelFilter, Log, Metadata, Record};
+
+struct StderrLogger;
+
+impl Log for StderrLogger {
+    fn enabled(&self, metadata: &Metadata) -> bool {
+        metadata.level() <= log::max_level()
+    }
+
+    fn log(&self, record: &Record) {
+        if self.enabled(record.metadata()) {
+            eprintln!(
+                "{:<5} {}: {}",
+                record.level(),
+                record.target(),
+                record.args()
+            );
+        }
+    }
+
+    fn flush(&self) {}
+}
+
+static LOGGER: StderrLogger = StderrLogger;
+
+/// Level for the `-v` count and `-q`: warnings by default, errors only when
+/// quiet, and info, debug then trace for each `-v`.
+pub fn level(verbose: u8, quiet: bool) -> LevelFilter {
+    match (quiet, verbose) {
+        (true, _) => LevelFilter::Error,
+        (false, 0) => LevelFilter::Warn,
+        (false, 1) => LevelFilter::Info,
+        (false, 2) => LevelFilter::Debug,
+        (false, _) => LevelFilter::Trace,
+    }
+}
+
+pub fn init(level: LevelFilter) {
+    log::set_logger(&LOGGER).expect("the logger is only set once");
+    log::set_max_level(level);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn verbosity_levels() {
+        assert_eq!(level(0, false), LevelFilter::Warn);
+        assert_eq!(level(1, false), LevelFilter::Info);
+        assert_eq!(level(2, false), LevelFilter::Debug);
+        assert_eq!(level(5, false), LevelFilter::Trace);
+        assert_eq!(level(0, true), LevelFilter::Error);
+    }
+}
@@ -0,0 +1,511 @@
+use std::path::{Path, PathBuf};
+use std::time::Duration;
+
+use clap::{Args, Parser, Subcommand, ValueEnum};
+
+use aoc_common::profile::CountingAllocator;
+use aoc_common::{measure, par, Answers, Check, Input, Result, Stats, TrailingBlanks};
+
+mod days;
+mod fetch;
+mod logger;
+mod report;
+mod scaffold;
+mod serve;
+
+use days::Day;
+use report::{DayResult, PartResult};
+
+#[global_allocator]
+static GLOBAL: CountingAllocator = CountingAllocator;
+
+const WORKSPACE: &str = concat!(env!("CARGO_MANIFEST_DIR"), "/..");
+const DEFAULT_ANSWERS: &str = concat!(env!("CARGO_MANIFEST_DIR"), "/../answers.txt");
+
+#[derive(Parser)]
+#[command(name = "aoc2018", about = "Advent of Code 2018 solutions")]
+struct Cli {
+    /// Output format. `json` prints one object per line: per part for the
+    /// commands that solve, per day for `list`, one for the others
+    #[arg(long, value_enum, global = true, default_value_t = Format::Table)]
+    format: Format,
+    /// Log more about what the solvers do, repeat for more detail
+    #[arg(short, long, action = clap::ArgAction::Count, global = true)]
+    verbose: u8,
+    /// Only log errors
+    #[arg(short, long, global = true, conflicts_with = "verbose")]
+    quiet: bool,
+    /// Treat blank lines at the end of an input as an error rather than
+    /// skipping them
+    #[arg(long, global = true)]
+    reject_trailing_blanks: bool,
+    #[command(subcommand)]
+    command: Command,
+}
+
+#[derive(Clone, Copy, PartialEq, ValueEnum)]
+enum Format {
+    Table,
+    Json,
+}
+
+#[derive(Subcommand)]
+enum Command {
+    /// Run a single day, or `all` of them
+    Run {
+        #[command(flatten)]
+        selection: Selection,
+        #[command(flatten)]
+        input: InputArg,
+        /// Also draw the selected part (1 by default) as a .ppm, .png or
+        /// .svg picture. Only for days 3, 4 and 6
+        #[arg(long, value_name = "FILE")]
+        render: Option<PathBuf>,
+    },
+    /// Run and check the answers against the known answers file
+    Verify {
+        #[command(flatten)]
+        selection: Selection,
+        #[command(flatten)]
+        input: InputArg,
+        /// Store answers that are not in the answers file yet. Only use this
+        /// once the answers have been confirmed as correct
+        #[arg(long)]
+        record: bool,
+    },
+    /// Run on every `*.txt` input in a directory and check the answers
+    Batch {
+        #[command(flatten)]
+        selection: Selection,
+        /// Directory with the inputs. Defaults to each day's `data` directory
+        #[arg(long, value_name = "DIR")]
+        dir: Option<PathBuf>,
+    },
+    /// Time and measure the peak heap usage of parsing and each part
+    Profile {
+        #[command(flatten)]
+        selection: Selection,
+        #[command(flatten)]
+        input: InputArg,
+    },
+    /// Download a day's input to `dayN/data/NAME.txt`, unless it is there
+    /// already. NAME is set in the config and defaults to `input`
+    Fetch {
+        day: u32,
+        /// Config file with the session token. Defaults to `$AOC_CONFIG`,
+        /// otherwise to `~/.config/aoc2018/config`
+        #[arg(long, value_name = "FILE")]
+        config: Option<PathBuf>,
+    },
+    /// Create the crate for a new day and register it in the workspace
+    NewDay {
+        day: u32,
+        /// Title of the puzzle
+        #[arg(long)]
+        title: String,
+    },
+    /// Solve inputs sent over HTTP: POST the input to `/day/N/part/P` for
+    /// the JSON record of `--format json`, GET `/days` for the days
+    Serve {
+        /// Address to listen on, only the local machine by default
+        #[arg(long, default_value = "127.0.0.1:8018")]
+        addr: String,
+        /// Seconds a solve may take before it is answered with 504
+        #[arg(long, default_value_t = 10)]
+        timeout: u64,
+        /// File with the known answers
+        #[arg(long, value_name = "FILE", default_value = DEFAULT_ANSWERS)]
+        answers: PathBuf,
+    },
+    /// Print generated input for a day, for stress tests and scaling
+    /// experiments
+    Generate {
+        day: u32,
+        #[arg(long, default_value_t = 1)]
+        seed: u64,
+        /// Number of records: lines, box IDs, claims, shifts, polymer units
+        /// or coordinates, depending on the day
+        #[arg(long, default_value_t = 1000)]
+        size: usize,
+    },
+    /// List the available days
+    List,
+}
+
+#[derive(Args)]
+struct Selection {
+    /// Day number or `all`
+    day: String,
+    /// Only run the given part
+    #[arg(long, value_parser = clap::value_parser!(u32).range(1..=2))]
+    part: Option<u32>,
+    /// Solve with a named alternative implementation instead, see `list`
+    #[arg(long, value_name = "NAME")]
+    variant: Option<String>,
+    /// File with the known answers
+    #[arg(long, value_name = "FILE", default_value = DEFAULT_ANSWERS)]
+    answers: PathBuf,
+}
+
+#[derive(Args)]
+struct InputArg {
+    /// Input file, or `-` to read stdin. Defaults to `$AOC_INPUT_DIR/dayN.txt`
+    /// if that variable is set, otherwise to the day's bundled input
+    #[arg(long, value_name = "FILE")]
+    input: Option<String>,
+}
+
+fn select_days(day: &str) -> Result<Vec<&'static Day>> {
+    if day == "all" {
+        return Ok(days::DAYS.to_vec());
+    }
+    let number: u32 = day
+        .parse()
+        .map_err(|_| format!("Invalid day '{}', expected a number or 'all'", day))?;
+    let day = days::find_day(number).ok_or_else(|| format!("Day {} is not solved yet", number))?;
+    Ok(vec![day])
+}
+
+/// Parse `input` once and solve each of `parts` on it, with `variant` if given.
+fn run_day(
+    day: &Day,
+    parts: &[u32],
+    variant: Option<&str>,
+    input: &Input,
+    answers: &Answers,
+) -> Result<DayResult> {
+    let input_hash = input.hash()?;
+    let input_name = match input {
+        Input::File(path) => path
+            .file_name()
+            .map_or_else(|| input.to_string(), |f| f.to_string_lossy().into_owned()),
+        Input::Text { name, .. } => name.clone(),
+    };
+    log::info!("Day {}: solving {}", day.day(), input);
+    let (parsed, parse_stats) = measure(|| day.parse(input));
+    let parsed = parsed.map_err(|e| e.to_string());
+    let mut results = Vec::new();
+    for &part in parts {
+        let (answer, stats) = match &parsed {
+            Ok(parsed) => measure(|| match variant {
+                Some(name) => day.solve_variant(input, parsed.as_ref(), name),
+                None => day.solve(parsed.as_ref(), part),
+            }),
+            Err(_) => (
+                Err("the input could not be parsed".into()),
+                Stats::default(),
+            ),
+        };
+        let answer = answer.map_err(|e| e.to_string());
+        let check = match &answer {
+            Ok(answer) => answers.check(day.day(), part, &input_hash, answer),
+            Err(_) => Check::Unknown,
+        };
+        results.push(PartResult {
+            part,
+            variant: variant.map(str::to_string),
+            answer,
+            check,
+            recorded: false,
+            stats,
+        });
+    }
+    Ok(DayResult {
+        day: day.day(),
+        input: input_name,
+        input_hash,
+        parse: parsed.map(|_| parse_stats),
+        parts: results,
+    })
+}
+
+/// Run the selected days and parts on the inputs returned by `inputs`.
+///
+/// With the `parallel` feature the days run concurrently, unless
+/// `concurrent` is false. The results are in day and input order either way.
+fn solve<F>(
+    selection: &Selection,
+    answers: &Answers,
+    concurrent: bool,
+    inputs: F,
+) -> Result<Vec<DayResult>>
+where
+    F: Fn(&Day) -> Result<Vec<Input>>,
+{
+    let days = select_days(&selection.day)?;
+    let variant = selection.variant.as_deref();
+    let parts: Vec<u32> = match (variant, selection.part) {
+        (Some(name), part) => {
+            if days.len() > 1 {
+                return Err("--variant can only be used when running a single day".into());
+            }
+            let (_, variant_part) = days[0]
+                .variants()
+                .into_iter()
+                .find(|&(n, _)| n == name)
+                .ok_or_else(|| format!("Day {} has no variant '{}'", days[0].day(), name))?;
+            if part.is_some_and(|p| p != variant_part) {
+                return Err(format!("Variant '{}' solves part {}", name, variant_part).into());
+            }
+            vec![variant_part]
+        }
+        (None, Some(p)) => vec![p],
+        (None, None) => vec![1, 2],
+    };
+    let mut jobs = Vec::new();
+    for day in days {
+        for input in inputs(day)? {
+            jobs.push((day, input));
+        }
+    }
+    let run = |(day, input): &(&Day, Input)| {
+        run_day(*day, &parts, variant, input, answers).map_err(|e| e.to_string())
+    };
+    let results = if concurrent {
+        par::map(&jobs, run)
+    } else {
+        jobs.iter().map(run).collect()
+    };
+    results.into_iter().map(|r| r.map_err(Into::into)).collect()
+}
+
+fn solve_single(
+    selection: &Selection,
+    input: &InputArg,
+    answers: &Answers,
+    concurrent: bool,
+) -> Result<Vec<DayResult>> {
+    let input = input.input.as_deref();
+    if input.is_some() && select_days(&selection.day)?.len() > 1 {
+        return Err("--input can only be used when running a single day".into());
+    }
+    solve(selection, answers, concurrent, |day| {
+        let input = aoc_common::resolve_input(day.day(), input, Path::new(day.default_input()))?;
+        Ok(vec![input])
+    })
+}
+
+/// Whether the days can run concurrently. Not for JSON, as the records have
+/// heap figures, which are only right when one day runs at a time.
+fn concurrent(format: Format) -> bool {
+    format == Format::Table
+}
+
+/// Print `results` as JSON, or with `table` for the table format.
+fn output(results: &[DayResult], format: Format, table: fn(&[DayResult])) {
+    match format {
+        Format::Table => table(results),
+        Format::Json => report::print_json(results),
+    }
+}
+
+fn run(
+    selection: &Selection,
+    input: &InputArg,
+    render: Option<&Path>,
+    format: Format,
+) -> Result<bool> {
+    let answers = Answers::load(&selection.answers)?;
+    let results = match render {
+        Some(path) => {
+            // solve the input that was drawn, stdin can only be read once
+            let drawn = render_picture(selection, input, path)?;
+            solve(selection, &answers, concurrent(format), |_| {
+                Ok(vec![drawn.clone()])
+            })?
+        }
+        None => solve_single(selection, input, &answers, concurrent(format))?,
+    };
+    output(&results, format, report::print_table);
+    Ok(!results.iter().any(DayResult::is_error))
+}
+
+/// Draw the selected day and part to `path` and return the input used.
+fn render_picture(selection: &Selection, input: &InputArg, path: &Path) -> Result<Input> {
+    let day = match select_days(&selection.day)?[..] {
+        [day] => day,
+        _ => return Err("--render can only be used when running a single day".into()),
+    };
+    let render = days::find_renderer(day.day())
+        .ok_or_else(|| format!("Day {} has nothing to render", day.day()))?;
+    let input = aoc_common::resolve_input(
+        day.day(),
+        input.input.as_deref(),
+        Path::new(day.default_input()),
+    )?;
+    render(&input, selection.part.unwrap_or(1))?.save(path)?;
+    log::info!("Day {}: drew {}", day.day(), path.display());
+    Ok(input)
+}
+
+fn profile(selection: &Selection, input: &InputArg, format: Format) -> Result<bool> {
+    let answers = Answers::load(&selection.answers)?;
+    // the heap figures are only right when one day runs at a time
+    let results = solve_single(selection, input, &answers, false)?;
+    output(&results, format, report::print_profile);
+    Ok(!results.iter().any(DayResult::is_error))
+}
+
+fn verify(selection: &Selection, input: &InputArg, record: bool, format: Format) -> Result<bool> {
+    let mut answers = Answers::load(&selection.answers)?;
+    let mut results = solve_single(selection, input, &answers, concurrent(format))?;
+    if record {
+        for r in results.iter_mut() {
+            for p in r.parts.iter_mut().filter(|p| p.check == Check::Unknown) {
+                if let Ok(answer) = &p.answer {
+                    answers.insert(r.day, p.part, &r.input_hash, answer);
+                    p.recorded = true;
+                }
+            }
+        }
+        answers.save(&selection.answers)?;
+    }
+    output(&results, format, report::print_table);
+    Ok(!results.iter().any(|r| r.is_error() || r.is_fail()))
+}
+
+fn batch(selection: &Selection, dir: Option<&Path>, format: Format) -> Result<bool> {
+    let answers = Answers::load(&selection.answers)?;
+    let results = solve(selection, &answers, concurrent(format), |day| {
+        let dir = match dir {
+            Some(dir) => dir,
+            None => Path::new(day.default_input()).parent().unwrap(),
+        };
+        aoc_common::list_inputs(dir)
+    })?;
+    output(&results, format, |results| {
+        report::print_table(results);
+        report::print_summary(results);
+    });
+    Ok(!results.iter().any(|r| r.is_error() || r.is_fail()))
+}
+
+fn fetch(day: u32, config: Option<PathBuf>, format: Format) -> Result<bool> {
+    let config = config
+        .or_else(fetch::default_config_path)
+        .ok_or_else(|| format!("No config file, set {} or use --config", fetch::CONFIG_VAR))?;
+    let config = fetch::Config::load(&config)?;
+    let dest = &config.input_path(&Path::new(WORKSPACE).canonicalize()?, day);
+    let downloaded = match fetch::fetch(&config, day, dest)? {
+        fetch::Fetched::Cached => false,
+        fetch::Fetched::Downloaded => true,
+    };
+    match format {
+        Format::Table if downloaded => println!("Day {}: downloaded {}", day, dest.display()),
+        Format::Table => println!("Day {}: already have {}", day, dest.display()),
+        Format::Json => println!("{}", report::fetch_record(day, dest, downloaded)),
+    }
+    Ok(true)
+}
+
+fn new_day(day: u32, title: &str, format: Format) -> Result<bool> {
+    let root = Path::new(WORKSPACE).canonicalize()?;
+    let created: Vec<PathBuf> = scaffold::new_day(&root, day, title)?
+        .into_iter()
+        .map(|path| match path.strip_prefix(&root) {
+            Ok(relative) => relative.to_path_buf(),
+            Err(_) => path,
+        })
+        .collect();
+    match format {
+        Format::Table => {
+            for path in &created {
+                println!("{}", path.display());
+            }
+            println!(
+                "\nNext: 'aoc2018 fetch {}' for the input, then fill in day{}/src/lib.rs",
+                day, day
+            );
+        }
+        Format::Json => println!("{}", report::new_day_record(day, title, &created)),
+    }
+    Ok(true)
+}
+
+fn serve(addr: &str, answers: &Path, solver: serve::Solver, format: Format) -> Result<bool> {
+    // the solves load the answers themselves, but a broken file is better
+    // found before the first request
+    Answers::load(answers)?;
+    let server = serve::bind(addr)?;
+    let url = match server.server_addr().to_ip() {
+        Some(addr) => format!("http://{}", addr),
+        None => addr.to_string(),
+    };
+    match format {
+        Format::Table => println!("Listening on {}", url),
+        Format::Json => println!("{}", serde_json::json!({ "listening": url })),
+    }
+    serve::serve(&server, &solver);
+    Ok(true)
+}
+
+fn generate(day: u32, seed: u64, size: usize, format: Format) -> Result<bool> {
+    let generate =
+        days::find_generator(day).ok_or_else(|| format!("Day {} has no generator", day))?;
+    let input = generate(seed, size);
+    match format {
+        Format::Table => print!("{}", input),
+        Format::Json => println!("{}", report::generated_record(day, seed, size, &input)),
+    }
+    Ok(true)
+}
+
+fn list(format: Format) {
+    for day in days::DAYS {
+        match format {
+            Format::Table => {
+                println!("{:>3}  {}", day.day(), day.title());
+                for (name, part) in day.variants() {
+                    println!("       part {}: --variant {}", part, name);
+                }
+            }
+            Format::Json => println!("{}", report::day_record(*day)),
+        }
+    }
+}
+
+fn main() {
+    let cli = Cli::parse();
+    logger::init(logger::level(cli.verbose, cli.quiet));
+    if cli.reject_trailing_blanks {
+        aoc_common::set_trailing_blanks(TrailingBlanks::Reject);
+    }
+    let result = match cli.command {
+        Command::Run {
+            selection,
+            input,
+            render,
+        } => run(&selection, &input, render.as_deref(), cli.format),
+        Command::Verify {
+            selection,
+            input,
+            record,
+        } => verify(&selection, &input, record, cli.format),
+        Command::Batch { selection, dir } => batch(&selection, dir.as_deref(), cli.format),
+        Command::Profile { selection, input } => profile(&selection, &input, cli.format),
+        Command::Fetch { day, config } => fetch(day, config, cli.format),
+        Command::NewDay { day, title } => new_day(day, &title, cli.format),
+        Command::Serve {
+            addr,
+            answers,
+            timeout,
+        } => {
+            let solver = serve::Solver::new(&answers, Duration::from_secs(timeout))
+                .reject_trailing_blanks(cli.reject_trailing_blanks);
+            serve(&addr, &answers, solver, cli.format)
+        }
+        Command::Generate { day, seed, size } => generate(day, seed, size, cli.format),
+        Command::List => {
+            list(cli.format);
+            Ok(true)
+        }
+    };
+    match result {
+        Ok(true) => {}
+        Ok(false) => std::process::exit(1),
+        Err(e) => {
+            eprintln!("ERROR: {}", e);
+            std::process::exit(1);
+        }
+    }
+}
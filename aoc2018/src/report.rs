@@ -0,0 +1,336 @@
+use std::path::{Path, PathBuf};
+
+use serde::Serialize;
+use serde_json::json;
+
+use aoc_common::{Check, Stats};
+
+use crate::days::Day;
+
+/// Outcome of running one day on one input.
+pub struct DayResult {
+    pub day: u32,
+    pub input: String,
+    pub input_hash: String,
+    /// Parse statistics, or the parse error.
+    pub parse: Result<Stats, String>,
+    pub parts: Vec<PartResult>,
+}
+
+/// Outcome of a single part.
+pub struct PartResult {
+    pub part: u32,
+    /// Name of the variant used instead of the default implementation.
+    pub variant: Option<String>,
+    pub answer: Result<String, String>,
+    pub check: Check,
+    pub recorded: bool,
+    pub stats: Stats,
+}
+
+impl DayResult {
+    pub fn is_error(&self) -> bool {
+        self.parts.iter().any(PartResult::is_error)
+    }
+
+    pub fn is_fail(&self) -> bool {
+        self.parts.iter().any(PartResult::is_fail)
+    }
+}
+
+impl PartResult {
+    pub fn is_error(&self) -> bool {
+        self.answer.is_err()
+    }
+
+    pub fn is_fail(&self) -> bool {
+        matches!(self.check, Check::Fail { .. })
+    }
+
+    /// The part number, followed by the variant if there is one.
+    fn label(&self) -> String {
+        match &self.variant {
+            Some(variant) => format!("{} ({})", self.part, variant),
+            None => self.part.to_string(),
+        }
+    }
+
+    /// Short machine friendly status.
+    fn check_name(&self) -> &'static str {
+        match (&self.answer, &self.check) {
+            (Err(_), _) => "error",
+            (Ok(_), _) if self.recorded => "recorded",
+            (Ok(_), Check::Pass) => "pass",
+            (Ok(_), Check::Fail { .. }) => "fail",
+            (Ok(_), Check::Unknown) => "unknown",
+        }
+    }
+
+    fn status(&self) -> String {
+        match (&self.answer, &self.check) {
+            (Err(_), _) => "ERROR".to_string(),
+            (Ok(_), _) if self.recorded => "recorded".to_string(),
+            (Ok(_), Check::Pass) => "pass".to_string(),
+            (Ok(_), Check::Fail { expected }) => format!("FAIL (expected {})", expected),
+            (Ok(_), Check::Unknown) => "unknown".to_string(),
+        }
+    }
+}
+
+fn format_bytes(bytes: usize) -> String {
+    const UNITS: [&str; 4] = ["B", "KiB", "MiB", "GiB"];
+    let mut value = bytes as f64;
+    let mut unit = 0;
+    while value >= 1024.0 && unit < UNITS.len() - 1 {
+        value /= 1024.0;
+        unit += 1;
+    }
+    if unit == 0 {
+        format!("{} B", bytes)
+    } else {
+        format!("{:.1} {}", value, UNITS[unit])
+    }
+}
+
+fn print_errors(results: &[DayResult]) {
+    for r in results {
+        if let Err(e) = &r.parse {
+            eprintln!("\nDay {} ({}): {}", r.day, r.input, e);
+            continue;
+        }
+        for p in &r.parts {
+            if let Err(e) = &p.answer {
+                eprintln!("\nDay {} part {} ({}): {}", r.day, p.part, r.input, e);
+            }
+        }
+    }
+}
+
+pub fn print_table(results: &[DayResult]) {
+    let part_width = results
+        .iter()
+        .flat_map(|r| &r.parts)
+        .map(|p| p.label().len())
+        .fold(4, usize::max);
+    println!(
+        "{:>3}  {:>part_width$}  {:<16}  {:<28}  {:>12}  Check",
+        "Day", "Part", "Input", "Answer", "Time"
+    );
+    for r in results {
+        for p in &r.parts {
+            let (answer, time) = match &p.answer {
+                Ok(answer) => (answer.as_str(), format!("{:.3?}", p.stats.elapsed)),
+                Err(_) => ("-", "-".to_string()),
+            };
+            println!(
+                "{:>3}  {:>part_width$}  {:<16}  {:<28}  {:>12}  {}",
+                r.day,
+                p.label(),
+                r.input,
+                answer,
+                time,
+                p.status()
+            );
+        }
+    }
+    print_errors(results);
+}
+
+/// Time and peak heap usage of every stage, parsing included.
+pub fn print_profile(results: &[DayResult]) {
+    println!(
+        "{:>3}  {:<16}  {:<6}  {:>12}  {:>10}",
+        "Day", "Input", "Stage", "Time", "Peak heap"
+    );
+    let row = |r: &DayResult, stage: &str, stats: &Stats| {
+        println!(
+            "{:>3}  {:<16}  {:<6}  {:>12}  {:>10}",
+            r.day,
+            r.input,
+            stage,
+            format!("{:.3?}", stats.elapsed),
+            format_bytes(stats.peak_heap)
+        );
+    };
+    for r in results {
+        match &r.parse {
+            Ok(stats) => row(r, "parse", stats),
+            Err(_) => continue,
+        }
+        for p in r.parts.iter().filter(|p| p.answer.is_ok()) {
+            row(r, &format!("part {}", p.label()), &p.stats);
+        }
+    }
+    print_errors(results);
+}
+
+/// One part of one day on one input, as printed by `--format json`.
+#[derive(Serialize)]
+pub struct Record<'a> {
+    day: u32,
+    part: u32,
+    variant: Option<&'a str>,
+    input: &'a str,
+    input_hash: &'a str,
+    answer: Option<&'a str>,
+    check: &'static str,
+    expected: Option<&'a str>,
+    error: Option<&'a str>,
+    parse_ns: Option<u64>,
+    parse_peak_heap: Option<usize>,
+    solve_ns: Option<u64>,
+    solve_peak_heap: Option<usize>,
+}
+
+impl<'a> Record<'a> {
+    pub fn new(r: &'a DayResult, p: &'a PartResult) -> Record<'a> {
+        let parse = r.parse.as_ref().ok();
+        let solved = p.answer.is_ok();
+        Record {
+            day: r.day,
+            part: p.part,
+            variant: p.variant.as_deref(),
+            input: &r.input,
+            input_hash: &r.input_hash,
+            answer: p.answer.as_deref().ok(),
+            check: p.check_name(),
+            expected: match &p.check {
+                Check::Fail { expected } => Some(expected),
+                _ => None,
+            },
+            error: r
+                .parse
+                .as_ref()
+                .err()
+                .or(p.answer.as_ref().err())
+                .map(String::as_str),
+            parse_ns: parse.map(|s| s.elapsed.as_nanos() as u64),
+            parse_peak_heap: parse.map(|s| s.peak_heap),
+            solve_ns: Some(p.stats.elapsed.as_nanos() as u64).filter(|_| solved),
+            solve_peak_heap: Some(p.stats.peak_heap).filter(|_| solved),
+        }
+    }
+}
+
+/// Print one JSON object per line for every part.
+pub fn print_json(results: &[DayResult]) {
+    for r in results {
+        for p in &r.parts {
+            println!("{}", serde_json::to_string(&Record::new(r, p)).unwrap());
+        }
+    }
+}
+
+/// A day with its title and variants, as listed by `list --format json`.
+pub fn day_record(day: &Day) -> serde_json::Value {
+    let variants: Vec<_> = day
+        .variants()
+        .into_iter()
+        .map(|(name, part)| serde_json::json!({ "name": name, "part": part }))
+        .collect();
+    serde_json::json!({
+        "day": day.day(),
+        "title": day.title(),
+        "variants": variants,
+    })
+}
+
+/// An input saved by `fetch`, downloaded or already there.
+pub fn fetch_record(day: u32, path: &Path, downloaded: bool) -> serde_json::Value {
+    json!({ "day": day, "path": path, "downloaded": downloaded })
+}
+
+/// The files `new-day` created, relative to the workspace.
+pub fn new_day_record(day: u32, title: &str, created: &[PathBuf]) -> serde_json::Value {
+    json!({ "day": day, "title": title, "created": created })
+}
+
+/// Input made up by `generate`.
+pub fn generated_record(day: u32, seed: u64, size: usize, input: &str) -> serde_json::Value {
+    json!({ "day": day, "seed": seed, "size": size, "input": input })
+}
+
+pub fn print_summary(results: &[DayResult]) {
+    let parts: Vec<&PartResult> = results.iter().flat_map(|r| &r.parts).collect();
+    let count = |f: &dyn Fn(&PartResult) -> bool| parts.iter().filter(|p| f(p)).count();
+    println!(
+        "\n{} results: {} pass, {} fail, {} unknown, {} error",
+        parts.len(),
+        count(&|p| p.answer.is_ok() && p.check == Check::Pass),
+        count(&|p| p.is_fail()),
+        count(&|p| p.answer.is_ok() && p.check == Check::Unknown),
+        count(&|p| p.is_error()),
+    );
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::time::Duration;
+
+    #[test]
+    fn json_record() {
+        let stats = Stats {
+            elapsed: Duration::from_nanos(1500),
+            peak_heap: 64,
+        };
+        let result = DayResult {
+            day: 5,
+            input: "input.txt".to_string(),
+            input_hash: "0123456789abcdef".to_string(),
+            parse: Ok(stats),
+            parts: vec![PartResult {
+                part: 2,
+                variant: None,
+                answer: Ok("6188".to_string()),
+                check: Check::Fail {
+                    expected: "6189".to_string(),
+                },
+                recorded: false,
+                stats,
+            }],
+        };
+        let json = serde_json::to_value(Record::new(&result, &result.parts[0])).unwrap();
+        assert_eq!(
+            json,
+            serde_json::json!({
+                "day": 5,
+                "part": 2,
+                "variant": null,
+                "input": "input.txt",
+                "input_hash": "0123456789abcdef",
+                "answer": "6188",
+                "check": "fail",
+                "expected": "6189",
+                "error": null,
+                "parse_ns": 1500,
+                "parse_peak_heap": 64,
+                "solve_ns": 1500,
+                "solve_peak_heap": 64,
+            })
+        );
+    }
+
+    #[test]
+    fn command_records() {
+        let fetched = fetch_record(3, Path::new("day3/data/input.txt"), true);
+        assert_eq!(fetched["path"], "day3/data/input.txt");
+        assert_eq!(fetched["downloaded"], true);
+        let created = [PathBuf::from("day7/Cargo.toml")];
+        let new_day = new_day_record(7, "The Sum of Its Parts", &created);
+        assert_eq!(new_day["created"], json!(["day7/Cargo.toml"]));
+        let generated = generated_record(1, 42, 2, "+1\n-1\n");
+        assert_eq!(
+            generated,
+            json!({ "day": 1, "seed": 42, "size": 2, "input": "+1\n-1\n" })
+        );
+    }
+
+    #[test]
+    fn bytes() {
+        assert_eq!(format_bytes(0), "0 B");
+        assert_eq!(format_bytes(1023), "1023 B");
+        assert_eq!(format_bytes(1536), "1.5 KiB");
+        assert_eq!(format_bytes(3 * 1024 * 1024), "3.0 MiB");
+    }
+}
@@ -0,0 +1,213 @@
+//! Creating the crate of a new day from the templates in `templates/` and
+//! wiring it into the workspace.
+//!
+//! Workspace files get a line inserted above their `new-day:` marker comment,
+//! so the markers have to stay where they are.
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use aoc_common::Result;
+
+const CARGO_TOML: &str = include_str!("../templates/Cargo.toml.tmpl");
+const LIB_RS: &str = include_str!("../templates/lib.rs.tmpl");
+const BENCH_RS: &str = include_str!("../templates/bench.rs.tmpl");
+const FUZZ_TARGET_RS: &str = include_str!("../templates/fuzz_target.rs.tmpl");
+
+const FUZZ_TARGET_BIN: &str = r#"
+[[bin]]
+name = "day$DAY"
+path = "fuzz_targets/day$DAY.rs"
+test = false
+doc = false
+"#;
+
+/// Line to add to a workspace file, above its marker.
+struct Insert {
+    file: &'static str,
+    marker: &'static str,
+    line: &'static str,
+}
+
+const INSERTS: &[Insert] = &[
+    Insert {
+        file: "Cargo.toml",
+        marker: "# new-day: members",
+        line: r#""day$DAY","#,
+    },
+    Insert {
+        file: "aoc2018/Cargo.toml",
+        marker: "# new-day: dependencies",
+        line: r#"day$DAY = { path = "../day$DAY" }"#,
+    },
+    Insert {
+        file: "aoc2018/src/days.rs",
+        marker: "// new-day: days",
+        line: "&day$DAY::Day$DAY,",
+    },
+    Insert {
+        file: "fuzz/Cargo.toml",
+        marker: "# new-day: dependencies",
+        line: r#"day$DAY = { path = "../day$DAY" }"#,
+    },
+];
+
+fn fill(template: &str, day: u32, title: &str) -> String {
+    template
+        .replace("$DAY", &day.to_string())
+        .replace("$TITLE", &title.replace('\\', "\\\\").replace('"', "\\\""))
+}
+
+/// `text` with `line` inserted above the `marker` line, indented like it.
+fn insert_above(text: &str, marker: &str, line: &str) -> Option<String> {
+    let mut result = String::new();
+    let mut found = false;
+    for l in text.lines() {
+        if l.trim() == marker && !found {
+            let indent = &l[..l.len() - l.trim_start().len()];
+            result += &format!("{}{}\n", indent, line);
+            found = true;
+        }
+        result += l;
+        result.push('\n');
+    }
+    Some(result).filter(|_| found)
+}
+
+/// Create `day<day>` under the workspace `root` and register it everywhere.
+/// Returns the files created or changed.
+pub fn new_day(root: &Path, day: u32, title: &str) -> Result<Vec<PathBuf>> {
+    // the data directory may be there already, from fetching the input
+    let dir = root.join(format!("day{}", day));
+    if dir.join("Cargo.toml").exists() {
+        return Err(format!("'{}' already exists", dir.display()).into());
+    }
+
+    // Prepare every change before writing anything, so that a missing marker
+    // doesn't leave a half registered day behind.
+    let mut files: Vec<(PathBuf, String)> = Vec::new();
+    for insert in INSERTS {
+        let path = root.join(insert.file);
+        let text = fs::read_to_string(&path)
+            .map_err(|e| format!("Unable to read '{}': {}", path.display(), e))?;
+        let text = insert_above(&text, insert.marker, &fill(insert.line, day, title))
+            .ok_or_else(|| format!("'{}' has no '{}' line", path.display(), insert.marker))?;
+        files.push((path, text));
+    }
+    let fuzz_manifest = files
+        .iter_mut()
+        .find(|(path, _)| path.ends_with("fuzz/Cargo.toml"));
+    fuzz_manifest.unwrap().1 += &fill(FUZZ_TARGET_BIN, day, title);
+
+    let answers = root.join("answers.txt");
+    let mut answers_text = fs::read_to_string(&answers).unwrap_or_default();
+    answers_text += &format!(
+        "# {}: {}, add the answers with 'aoc2018 verify {} --record'\n",
+        day, title, day
+    );
+    files.push((answers, answers_text));
+
+    let templates = [
+        ("Cargo.toml", CARGO_TOML),
+        ("src/lib.rs", LIB_RS),
+        ("benches/bench.rs", BENCH_RS),
+    ];
+    for (file, template) in templates.iter() {
+        files.push((dir.join(file), fill(template, day, title)));
+    }
+    let fuzz_target = format!("fuzz/fuzz_targets/day{}.rs", day);
+    files.push((root.join(fuzz_target), fill(FUZZ_TARGET_RS, day, title)));
+
+    fs::create_dir_all(dir.join("data"))?;
+    for (path, text) in &files {
+        fs::create_dir_all(path.parent().unwrap())?;
+        fs::write(path, text)?;
+    }
+    Ok(files.into_iter().map(|(path, _)| path).collect())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// A workspace with just the files `new_day` changes.
+    fn scratch_workspace(name: &str) -> PathBuf {
+        let root =
+            std::env::temp_dir().join(format!("aoc2018-scaffold-{}-{}", std::process::id(), name));
+        let _ = fs::remove_dir_all(&root);
+        for dir in &["aoc2018/src", "fuzz/fuzz_targets"] {
+            fs::create_dir_all(root.join(dir)).unwrap();
+        }
+        let files = [
+            (
+                "Cargo.toml",
+                "members = [\n    \"day1\",\n    # new-day: members\n]\n",
+            ),
+            (
+                "aoc2018/Cargo.toml",
+                "[dependencies]\n# new-day: dependencies\n",
+            ),
+            (
+                "aoc2018/src/days.rs",
+                "DAYS = &[\n    &day1::Day1,\n    // new-day: days\n];\n",
+            ),
+            (
+                "fuzz/Cargo.toml",
+                "[dependencies]\n# new-day: dependencies\n",
+            ),
+            ("answers.txt", "# day\tpart\tinput hash\tanswer\n"),
+        ];
+        for (file, text) in files.iter() {
+            fs::write(root.join(file), text).unwrap();
+        }
+        root
+    }
+
+    #[test]
+    fn creates_and_registers_day() {
+        let root = scratch_workspace("new");
+        new_day(&root, 7, "The Sum of Its Parts").unwrap();
+        let read = |file: &str| fs::read_to_string(root.join(file)).unwrap();
+
+        assert_eq!(
+            read("Cargo.toml"),
+            "members = [\n    \"day1\",\n    \"day7\",\n    # new-day: members\n]\n"
+        );
+        assert!(read("aoc2018/src/days.rs").contains("    &day7::Day7,\n    // new-day: days"));
+        assert!(read("aoc2018/Cargo.toml").contains("day7 = { path = \"../day7\" }\n# new-day"));
+        assert!(read("fuzz/Cargo.toml").contains("path = \"fuzz_targets/day7.rs\""));
+        assert!(read("answers.txt").contains("# 7: The Sum of Its Parts"));
+        assert!(read("day7/Cargo.toml").contains("name = \"day7\""));
+        let lib = read("day7/src/lib.rs");
+        assert!(lib.contains("pub struct Day7;"));
+        assert!(lib.contains("const TITLE: &'static str = \"The Sum of Its Parts\";"));
+        assert!(read("day7/benches/bench.rs").contains("use day7::Day7;"));
+        assert!(read("fuzz/fuzz_targets/day7.rs").contains("day7::Day7"));
+        assert!(root.join("day7/data").is_dir());
+
+        let err = new_day(&root, 7, "Again").unwrap_err();
+        assert!(err.to_string().contains("already exists"), "{}", err);
+    }
+
+    #[test]
+    fn keeps_fetched_input() {
+        let root = scratch_workspace("fetched");
+        fs::create_dir_all(root.join("day9/data")).unwrap();
+        fs::write(root.join("day9/data/ann.txt"), "1, 1\n").unwrap();
+        new_day(&root, 9, "Marble Mania").unwrap();
+        assert!(root.join("day9/src/lib.rs").exists());
+        let input = fs::read_to_string(root.join("day9/data/ann.txt")).unwrap();
+        assert_eq!(input, "1, 1\n");
+    }
+
+    #[test]
+    fn missing_marker_changes_nothing() {
+        let root = scratch_workspace("marker");
+        fs::write(root.join("aoc2018/src/days.rs"), "DAYS = &[];\n").unwrap();
+        let err = new_day(&root, 8, "Memory Maneuver").unwrap_err();
+        assert!(err.to_string().contains("// new-day: days"), "{}", err);
+        assert!(!root.join("day8").exists());
+        assert!(!fs::read_to_string(root.join("Cargo.toml"))
+            .unwrap()
+            .contains("day8"));
+    }
+}
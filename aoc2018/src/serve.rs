@@ -0,0 +1,295 @@
+//! The solvers over HTTP, for tools that would rather not build the crates.
+//!
+//! `POST /day/N/part/P` with the input as the body solves it and answers
+//! with the record `--format json` prints, `?variant=NAME` picks a variant.
+//! `GET /days` lists the days like `list --format json`, as one array.
+//! Requests are handled one at a time, so the timings and heap figures are
+//! as accurate as those of `profile`. Every solve runs `run` in a process of
+//! its own, which is killed when it runs out of time, and the request is
+//! answered with 504.
+use std::env;
+use std::io::{Read, Write};
+use std::path::{Path, PathBuf};
+use std::process::{Command, Stdio};
+use std::sync::mpsc::{self, RecvTimeoutError};
+use std::thread;
+use std::time::Duration;
+
+use serde_json::{json, Value};
+use tiny_http::{Header, Method, Request, Response, Server};
+
+use aoc_common::Result;
+
+use crate::days::{self, Day};
+use crate::report;
+
+/// Largest input accepted, five times the largest puzzle input. This only
+/// bounds the memory a request takes: a few KiB of day 6 coordinates can
+/// keep a solver busy for hours, so the deadline is what bounds the time.
+const MAX_INPUT: u64 = 256 << 10;
+
+pub fn bind(addr: &str) -> Result<Server> {
+    Server::http(addr).map_err(|e| format!("Unable to listen on {}: {}", addr, e).into())
+}
+
+/// Answer requests for as long as the server runs, solving with `solver`.
+pub fn serve(server: &Server, solver: &Solver) {
+    for request in server.incoming_requests() {
+        respond(request, solver);
+    }
+}
+
+/// Runs every solve as a `run` command of this runner, so that it can be
+/// killed at the deadline.
+pub struct Solver {
+    exe: PathBuf,
+    answers: PathBuf,
+    timeout: Duration,
+    reject_trailing_blanks: bool,
+}
+
+impl Solver {
+    /// Check against the answers file at `answers` and give every solve
+    /// `timeout`.
+    pub fn new(answers: &Path, timeout: Duration) -> Solver {
+        Solver {
+            // fall back to the PATH if the runner cannot find itself
+            exe: env::current_exe().unwrap_or_else(|_| PathBuf::from("aoc2018")),
+            answers: answers.to_path_buf(),
+            timeout,
+            reject_trailing_blanks: false,
+        }
+    }
+
+    /// Pass on `--reject-trailing-blanks` to the solves.
+    pub fn reject_trailing_blanks(mut self, reject: bool) -> Solver {
+        self.reject_trailing_blanks = reject;
+        self
+    }
+
+    fn command(&self, day: &Day, part: u32, variant: Option<&str>) -> Command {
+        let mut command = Command::new(&self.exe);
+        command
+            .args(["-q", "--format", "json", "run", &day.day().to_string()])
+            .args(["--part", &part.to_string(), "--input", "-", "--answers"])
+            .arg(&self.answers);
+        if let Some(name) = variant {
+            command.args(["--variant", name]);
+        }
+        if self.reject_trailing_blanks {
+            command.arg("--reject-trailing-blanks");
+        }
+        command
+    }
+}
+
+fn respond(mut request: Request, solver: &Solver) {
+    let mut body = Vec::new();
+    let read = request
+        .as_reader()
+        .take(MAX_INPUT + 1)
+        .read_to_end(&mut body);
+    let (method, url) = (request.method().clone(), request.url().to_string());
+    let (status, json) = match read {
+        Err(e) => (400, error(format!("Unable to read the request: {}", e))),
+        Ok(_) if body.len() as u64 > MAX_INPUT => (413, error("The input is too large")),
+        Ok(_) => handle(&method, &url, body, solver),
+    };
+    log::info!("{} {}: {}", method, url, status);
+    let content_type = Header::from_bytes("Content-Type", "application/json").unwrap();
+    let response = Response::from_string(format!("{}\n", json))
+        .with_status_code(status)
+        .with_header(content_type);
+    if let Err(e) = request.respond(response) {
+        log::warn!("Unable to answer {} {}: {}", method, url, e);
+    }
+}
+
+fn error(message: impl Into<String>) -> Value {
+    json!({ "error": message.into() })
+}
+
+/// The status code and JSON body answering a request.
+fn handle(method: &Method, url: &str, body: Vec<u8>, solver: &Solver) -> (u16, Value) {
+    let (path, query) = match url.split_once('?') {
+        Some((path, query)) => (path, query),
+        None => (url, ""),
+    };
+    let segments: Vec<&str> = path.trim_matches('/').split('/').collect();
+    match (method, &segments[..]) {
+        (Method::Get, ["days"]) => {
+            let days: Vec<Value> = days::DAYS
+                .iter()
+                .map(|&day| report::day_record(day))
+                .collect();
+            (200, Value::Array(days))
+        }
+        (Method::Post, ["day", day, "part", part]) => match find_part(day, part, query) {
+            Ok((day, part, variant)) => solve(day, part, variant, body, solver),
+            Err((status, message)) => (status, error(message)),
+        },
+        (_, ["days"]) => (405, error("Use GET to list the days")),
+        (_, ["day", _, "part", _]) => (405, error("Use POST with the input to solve it")),
+        _ => (404, error(format!("Nothing at {}", path))),
+    }
+}
+
+type Status<T> = std::result::Result<T, (u16, String)>;
+
+/// The day, part and variant a solve request is for.
+fn find_part<'a>(
+    day: &str,
+    part: &str,
+    query: &'a str,
+) -> Status<(&'static Day, u32, Option<&'a str>)> {
+    let day = day
+        .parse()
+        .ok()
+        .and_then(days::find_day)
+        .ok_or_else(|| (404, format!("Day {} is not solved yet", day)))?;
+    let part = match part.parse() {
+        Ok(part @ 1..=2) => part,
+        _ => return Err((404, format!("Day {} has no part {}", day.day(), part))),
+    };
+    let variant = query
+        .split('&')
+        .find_map(|pair| pair.strip_prefix("variant="));
+    if let Some(name) = variant {
+        let (_, variant_part) = day
+            .variants()
+            .into_iter()
+            .find(|&(n, _)| n == name)
+            .ok_or_else(|| (404, format!("Day {} has no variant '{}'", day.day(), name)))?;
+        if variant_part != part {
+            let message = format!("Variant '{}' solves part {}", name, variant_part);
+            return Err((400, message));
+        }
+    }
+    Ok((day, part, variant))
+}
+
+fn solve(
+    day: &'static Day,
+    part: u32,
+    variant: Option<&str>,
+    body: Vec<u8>,
+    solver: &Solver,
+) -> (u16, Value) {
+    if std::str::from_utf8(&body).is_err() {
+        return (400, error("The input is not valid UTF-8"));
+    }
+    let mut child = match solver
+        .command(day, part, variant)
+        .stdin(Stdio::piped())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::null())
+        .spawn()
+    {
+        Ok(child) => child,
+        Err(e) => return (500, error(format!("Unable to start the solver: {}", e))),
+    };
+    // the solver reads all of its input before it writes anything, but the
+    // record can be larger than a pipe holds, so it is read on a thread
+    let mut stdin = child.stdin.take().unwrap();
+    let mut stdout = child.stdout.take().unwrap();
+    let (sender, receiver) = mpsc::channel();
+    thread::spawn(move || {
+        let mut output = String::new();
+        let read = stdout.read_to_string(&mut output);
+        // nobody listens any more if the solve ran out of time
+        let _ = sender.send(read.map(|_| output));
+    });
+    // a solver that fails early stops reading, which the output shows
+    let _ = stdin.write_all(&body);
+    drop(stdin);
+
+    let output = receiver.recv_timeout(solver.timeout);
+    if let Err(RecvTimeoutError::Timeout) = output {
+        let _ = child.kill();
+    }
+    let _ = child.wait();
+    match output {
+        Ok(Ok(output)) => match serde_json::from_str::<Value>(output.trim()) {
+            // the record has the parse error or the solver's error
+            Ok(record) if record["error"].is_null() => (200, record),
+            Ok(record) => (422, record),
+            Err(_) => (500, error("The solver crashed")),
+        },
+        Ok(Err(e)) => (500, error(format!("Unable to read the answer: {}", e))),
+        Err(RecvTimeoutError::Timeout) => {
+            let message = format!("The solve took longer than {:?}", solver.timeout);
+            (504, error(message))
+        }
+        Err(RecvTimeoutError::Disconnected) => (500, error("The solver crashed")),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // solving needs the runner binary, see tests/serve.rs, these only get
+    // as far as picking the day and part
+    const DAY1: &str = "+1\n-2\n+3\n+1\n";
+
+    fn solver() -> Solver {
+        Solver::new(Path::new("answers.txt"), Duration::from_secs(60))
+    }
+
+    fn post(url: &str, body: &str) -> (u16, Value) {
+        handle(&Method::Post, url, body.as_bytes().to_vec(), &solver())
+    }
+
+    #[test]
+    fn reports_errors() {
+        assert_eq!(post("/day/99/part/1", DAY1).0, 404);
+        assert_eq!(post("/day/1/part/3", DAY1).0, 404);
+        assert_eq!(post("/day/1/part/1?variant=nope", DAY1).0, 404);
+        assert_eq!(post("/day/1/part/1?variant=repeat1", DAY1).0, 400);
+        assert_eq!(post("/days", "").0, 405);
+        assert_eq!(post("/", "").0, 404);
+        let get = handle(&Method::Get, "/day/1/part/1", Vec::new(), &solver());
+        assert_eq!(get.0, 405);
+        let binary = handle(&Method::Post, "/day/1/part/1", vec![0xff], &solver());
+        assert_eq!(binary.0, 400);
+    }
+
+    #[test]
+    fn solver_command() {
+        let solver = solver().reject_trailing_blanks(true);
+        let command = solver.command(days::find_day(1).unwrap(), 2, Some("repeat1"));
+        let args: Vec<_> = command.get_args().map(|a| a.to_str().unwrap()).collect();
+        assert_eq!(
+            args.join(" "),
+            "-q --format json run 1 --part 2 --input - --answers answers.txt \
+             --variant repeat1 --reject-trailing-blanks"
+        );
+    }
+
+    #[test]
+    fn over_localhost() {
+        let server = bind("127.0.0.1:0").unwrap();
+        let url = format!("http://{}", server.server_addr().to_ip().unwrap());
+        let handler = thread::spawn(move || {
+            let solver = solver();
+            for request in server.incoming_requests().take(2) {
+                respond(request, &solver);
+            }
+        });
+
+        let response = ureq::get(&format!("{}/days", url)).call().unwrap();
+        assert_eq!(response.content_type(), "application/json");
+        let days: Value = serde_json::from_str(&response.into_string().unwrap()).unwrap();
+        assert_eq!(days[0]["title"], "Chronal Calibration");
+
+        let huge = "+1\n".repeat(MAX_INPUT as usize);
+        match ureq::post(&format!("{}/day/1/part/1", url)).send_string(&huge) {
+            Err(ureq::Error::Status(413, _)) => {}
+            other => panic!(
+                "expected a 413 response, got {:?}",
+                other.map(|r| r.status())
+            ),
+        }
+        handler.join().unwrap();
+    }
+}
@@ -0,0 +1,116 @@
+//! End to end tests of the runner commands that read and write files.
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::process::{Command, Output};
+
+use aoc_common::Input;
+
+/// An empty directory for the test called `name`.
+fn scratch_dir(name: &str) -> PathBuf {
+    let dir = std::env::temp_dir().join(format!("aoc2018-cli-{}-{}", std::process::id(), name));
+    let _ = fs::remove_dir_all(&dir);
+    fs::create_dir_all(&dir).unwrap();
+    dir
+}
+
+fn aoc2018(args: &[&str], answers: &Path) -> Output {
+    Command::new(env!("CARGO_BIN_EXE_aoc2018"))
+        .args(args)
+        .arg("--answers")
+        .arg(answers)
+        .output()
+        .unwrap()
+}
+
+fn hash(text: &str) -> String {
+    Input::text("test", text).hash().unwrap()
+}
+
+#[test]
+fn verify_records_new_answers() {
+    let dir = scratch_dir("record");
+    let (no_repeat, wrong) = ("+1\n+1\n", "+1\n-1\n");
+    fs::write(dir.join("no_repeat.txt"), no_repeat).unwrap();
+    fs::write(dir.join("wrong.txt"), wrong).unwrap();
+    let answers = dir.join("answers.txt");
+    let existing = format!(
+        "# day\tpart\tinput hash\tanswer\n6\t1\tffff\t3604\n1\t1\t{}\t7\n2\t2\teeee\tabc\n",
+        hash(wrong)
+    );
+    fs::write(&answers, &existing).unwrap();
+
+    for input in &["no_repeat.txt", "wrong.txt"] {
+        let input = dir.join(input);
+        let args = [
+            "-q",
+            "verify",
+            "1",
+            "--record",
+            "--input",
+            input.to_str().unwrap(),
+        ];
+        // part 2 has no answer for one input, part 1 a wrong one for the other
+        assert!(!aoc2018(&args, &answers).status.success());
+    }
+
+    // neither the error nor the wrong answer is recorded, and the known
+    // answers stay as they were
+    let recorded = format!(
+        "{}1\t1\t{}\t2\n1\t2\t{}\t0\n",
+        existing,
+        hash(no_repeat),
+        hash(wrong)
+    );
+    assert_eq!(fs::read_to_string(&answers).unwrap(), recorded);
+    fs::remove_dir_all(&dir).unwrap();
+}
+
+#[test]
+fn batch_reports_every_input() {
+    let dir = scratch_dir("batch");
+    // sorted first, so an error that stopped the batch would lose the other
+    fs::write(dir.join("a_malformed.txt"), "+1\nx\n").unwrap();
+    fs::write(dir.join("b_good.txt"), "+1\n-1\n").unwrap();
+    let args = [
+        "batch",
+        "1",
+        "--format",
+        "json",
+        "--dir",
+        dir.to_str().unwrap(),
+    ];
+    let output = aoc2018(&args, &dir.join("answers.txt"));
+    assert!(!output.status.success());
+
+    let records: Vec<serde_json::Value> = String::from_utf8(output.stdout)
+        .unwrap()
+        .lines()
+        .map(|line| serde_json::from_str(line).unwrap())
+        .collect();
+    let reported: Vec<(&str, u64, &str)> = records
+        .iter()
+        .map(|r| {
+            let input = r["input"].as_str().unwrap();
+            (
+                input,
+                r["part"].as_u64().unwrap(),
+                r["check"].as_str().unwrap(),
+            )
+        })
+        .collect();
+    assert_eq!(
+        reported,
+        [
+            ("a_malformed.txt", 1, "error"),
+            ("a_malformed.txt", 2, "error"),
+            ("b_good.txt", 1, "unknown"),
+            ("b_good.txt", 2, "unknown"),
+        ]
+    );
+    assert!(records[0]["error"]
+        .as_str()
+        .unwrap()
+        .contains("invalid number"));
+    assert_eq!(records[2]["answer"], "0");
+    fs::remove_dir_all(&dir).unwrap();
+}
@@ -0,0 +1,126 @@
+//! The `serve` command, answering requests with solves run by the runner.
+use std::io::{BufRead, BufReader};
+use std::process::{Child, Command, Stdio};
+use std::time::{Duration, Instant};
+
+use serde_json::Value;
+
+const DAY1: &str = "+1\n-2\n+3\n+1\n";
+
+/// A server on a free port, killed when dropped.
+struct Server {
+    child: Child,
+    url: String,
+}
+
+impl Server {
+    fn start(timeout: u64) -> Server {
+        let answers = std::env::temp_dir().join("aoc2018-serve-no-answers.txt");
+        let mut child = Command::new(env!("CARGO_BIN_EXE_aoc2018"))
+            .args(["serve", "--addr", "127.0.0.1:0", "--timeout"])
+            .arg(timeout.to_string())
+            .arg("--answers")
+            .arg(answers)
+            .stdout(Stdio::piped())
+            .spawn()
+            .unwrap();
+        let mut line = String::new();
+        let stdout = child.stdout.take().unwrap();
+        BufReader::new(stdout).read_line(&mut line).unwrap();
+        let url = line
+            .trim()
+            .strip_prefix("Listening on ")
+            .unwrap()
+            .to_string();
+        Server { child, url }
+    }
+
+    fn post(&self, path: &str, body: &str) -> (u16, Value) {
+        let response = match ureq::post(&format!("{}{}", self.url, path)).send_string(body) {
+            Ok(response) => response,
+            Err(ureq::Error::Status(_, response)) => response,
+            Err(e) => panic!("POST {} failed: {}", path, e),
+        };
+        let status = response.status();
+        (
+            status,
+            serde_json::from_str(&response.into_string().unwrap()).unwrap(),
+        )
+    }
+}
+
+impl Drop for Server {
+    fn drop(&mut self) {
+        let _ = self.child.kill();
+        let _ = self.child.wait();
+    }
+}
+
+#[test]
+fn solves_parts() {
+    let server = Server::start(60);
+    let (status, record) = server.post("/day/1/part/1", DAY1);
+    assert_eq!(status, 200);
+    assert_eq!(record["answer"], "3");
+    assert_eq!(record["check"], "unknown");
+    assert!(record["solve_ns"].is_u64());
+
+    let (status, record) = server.post("/day/1/part/2?variant=repeat1", DAY1);
+    assert_eq!((status, record["answer"].as_str()), (200, Some("2")));
+    assert_eq!(record["variant"], "repeat1");
+}
+
+#[test]
+fn reports_errors() {
+    let server = Server::start(60);
+    let (status, record) = server.post("/day/1/part/1", "+1\nx\n");
+    assert_eq!(status, 422);
+    let message = record["error"].as_str().unwrap();
+    assert!(message.contains("<stdin>:2:1"), "{}", message);
+    assert_eq!(record["answer"], Value::Null);
+
+    for path in &["/day/1/part/2", "/day/1/part/2?variant=repeat1"] {
+        let (status, record) = server.post(path, "+1\n");
+        assert_eq!(status, 422, "{}", path);
+        assert_eq!(record["error"], "There is no repeated sum");
+    }
+}
+
+#[test]
+fn too_much_work() {
+    // 2000 coordinates on a 1000x1000 grid, hours of work without the limit
+    let coords: String = (0..2000)
+        .map(|i| format!("{}, {}\n", i % 1000, i / 2))
+        .collect();
+    let server = Server::start(60);
+    let start = Instant::now();
+    let (status, record) = server.post("/day/6/part/1", &coords);
+    assert_eq!(status, 422);
+    let message = record["error"].as_str().unwrap();
+    assert!(message.contains("too much work"), "{}", message);
+    assert!(start.elapsed() < Duration::from_secs(10));
+}
+
+#[test]
+fn deadline() {
+    // box IDs that pairwise differ in at least two letters, so the quadratic
+    // variant compares every pair, tens of millions of them
+    let ids: String = (0..9000)
+        .map(|i: u32| {
+            let letters: String = (0..5)
+                .map(|d| char::from(b'a' + (i / 26u32.pow(d) % 26) as u8))
+                .collect();
+            format!("{}{}abcdefghijklmnop\n", letters, letters)
+        })
+        .collect();
+    let server = Server::start(1);
+    let start = Instant::now();
+    let (status, record) = server.post("/day/2/part/2?variant=boxes1", &ids);
+    assert_eq!(status, 504);
+    assert!(record["error"].as_str().unwrap().contains("1s"));
+    assert!(start.elapsed() < Duration::from_secs(5));
+
+    // the solve was killed, so the server is free again right away
+    let (status, record) = server.post("/day/1/part/1", DAY1);
+    assert_eq!((status, record["answer"].as_str()), (200, Some("3")));
+}
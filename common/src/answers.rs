@@ -0,0 +1,154 @@
+use std::collections::BTreeMap;
+use std::fs;
+use std::io;
+use std::path::Path;
+
+use crate::error::Result;
+
+/// How a computed answer compares to the registry.
+#[derive(Debug, Clone, PartialEq)]
+pub enum Check {
+    Pass,
+    Fail { expected: String },
+    Unknown,
+}
+
+/// Known answers keyed by day, part and input hash, so every teammate's
+/// input can be verified without hard-coding answers.
+///
+/// The file has one tab separated `day part hash answer` record per line.
+/// Blank lines and lines starting with `#` are ignored. Saving keeps the
+/// records in the order they were loaded, with new ones at the end, so that
+/// recording answers only adds lines to a hand-edited file.
+#[derive(Debug, Default)]
+pub struct Answers {
+    answers: BTreeMap<(u32, u32, String), String>,
+    order: Vec<(u32, u32, String)>,
+}
+
+impl Answers {
+    /// Load answers from `path`. A missing file is an empty registry.
+    pub fn load(path: &Path) -> Result<Answers> {
+        let text = match fs::read_to_string(path) {
+            Ok(text) => text,
+            Err(e) if e.kind() == io::ErrorKind::NotFound => return Ok(Answers::default()),
+            Err(e) => {
+                return Err(
+                    format!("Unable to read answers file '{}': {}", path.display(), e).into(),
+                )
+            }
+        };
+        Answers::parse(&text)
+            .map_err(|e| format!("Invalid answers file '{}': {}", path.display(), e).into())
+    }
+
+    fn parse(text: &str) -> std::result::Result<Answers, String> {
+        let mut answers = Answers::default();
+        for (index, line) in text.lines().enumerate() {
+            if line.trim().is_empty() || line.starts_with('#') {
+                continue;
+            }
+            let fields: Vec<&str> = line.splitn(4, '\t').collect();
+            let record = match fields[..] {
+                [day, part, hash, answer] => day
+                    .parse()
+                    .and_then(|day| part.parse().map(|part| (day, part, hash, answer))),
+                _ => {
+                    return Err(format!(
+                        "line {}: expected 4 tab separated fields",
+                        index + 1
+                    ))
+                }
+            };
+            let (day, part, hash, answer) =
+                record.map_err(|e| format!("line {}: {}", index + 1, e))?;
+            answers.insert(day, part, hash, answer);
+        }
+        Ok(answers)
+    }
+
+    pub fn get(&self, day: u32, part: u32, hash: &str) -> Option<&str> {
+        self.answers
+            .get(&(day, part, hash.to_string()))
+            .map(String::as_str)
+    }
+
+    pub fn insert(&mut self, day: u32, part: u32, hash: &str, answer: &str) {
+        let key = (day, part, hash.to_string());
+        if self
+            .answers
+            .insert(key.clone(), answer.to_string())
+            .is_none()
+        {
+            self.order.push(key);
+        }
+    }
+
+    pub fn check(&self, day: u32, part: u32, hash: &str, answer: &str) -> Check {
+        match self.get(day, part, hash) {
+            Some(expected) if expected == answer => Check::Pass,
+            Some(expected) => Check::Fail {
+                expected: expected.to_string(),
+            },
+            None => Check::Unknown,
+        }
+    }
+
+    pub fn save(&self, path: &Path) -> Result<()> {
+        let mut text = String::from("# day\tpart\tinput hash\tanswer\n");
+        for key in &self.order {
+            let (day, part, hash) = key;
+            text += &format!("{}\t{}\t{}\t{}\n", day, part, hash, self.answers[key]);
+        }
+        fs::write(path, text)
+            .map_err(|e| format!("Unable to write answers file '{}': {}", path.display(), e))?;
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn check_answers() {
+        let answers =
+            Answers::parse("# comment\n1\t2\tabc\t83445\n\n2\t2\tabc\tfoo bar\n").unwrap();
+        assert_eq!(answers.check(1, 2, "abc", "83445"), Check::Pass);
+        assert_eq!(
+            answers.check(1, 2, "abc", "1"),
+            Check::Fail {
+                expected: "83445".to_string()
+            }
+        );
+        assert_eq!(answers.check(1, 2, "def", "83445"), Check::Unknown);
+        assert_eq!(answers.check(2, 2, "abc", "foo bar"), Check::Pass);
+    }
+
+    #[test]
+    fn invalid_record() {
+        let err = Answers::parse("1\t2\tabc\n").unwrap_err();
+        assert!(err.starts_with("line 1:"), "{}", err);
+        assert!(Answers::parse("x\t2\tabc\t1\n").is_err());
+    }
+
+    #[test]
+    fn save_then_load() {
+        let path = std::env::temp_dir().join(format!("aoc2018-answers-{}.txt", std::process::id()));
+        let mut answers = Answers::parse("2\t1\tdef\t6944\n1\t2\tabc\t83445\n").unwrap();
+        answers.insert(1, 2, "abc", "83446");
+        answers.insert(1, 1, "abc", "590");
+        answers.save(&path).unwrap();
+        let text = fs::read_to_string(&path).unwrap();
+        let loaded = Answers::load(&path).unwrap();
+        fs::remove_file(&path).unwrap();
+
+        let records: Vec<&str> = text.lines().skip(1).collect();
+        assert_eq!(
+            records,
+            ["2\t1\tdef\t6944", "1\t2\tabc\t83446", "1\t1\tabc\t590"]
+        );
+        assert_eq!(loaded.order, answers.order);
+        assert_eq!(loaded.answers, answers.answers);
+    }
+}
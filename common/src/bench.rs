@@ -0,0 +1,72 @@
+//! Helpers for the per-day `bencher` suites, which time parsing and each
+//! part separately.
+use std::fs;
+use std::path::Path;
+
+use bencher::{black_box, Bencher, TDynBenchFn, TestDesc, TestDescAndFn, TestFn, TestName};
+
+use crate::input::Input;
+use crate::solution::{Solution, Variant};
+
+/// The contents of an input file, for building scaled-up inputs.
+pub fn read(path: &str) -> String {
+    fs::read_to_string(path).unwrap_or_else(|e| panic!("Unable to read '{}': {}", path, e))
+}
+
+/// Read an input file into memory, so that benchmarks don't measure disk I/O.
+pub fn load(path: &str) -> Input {
+    let name = Path::new(path).file_name().unwrap().to_string_lossy();
+    Input::text(&name, read(path))
+}
+
+fn input_len(input: &Input) -> u64 {
+    match input {
+        Input::Text { text, .. } => text.len() as u64,
+        Input::File(_) => 0,
+    }
+}
+
+pub fn parse<S: Solution>(b: &mut Bencher, input: &Input) {
+    b.bytes = input_len(input);
+    b.iter(|| S::parse(black_box(input)).unwrap())
+}
+
+pub fn part1<S: Solution>(b: &mut Bencher, input: &Input) {
+    let parsed = S::parse(input).unwrap();
+    b.iter(|| S::part1(black_box(&parsed)).unwrap())
+}
+
+pub fn part2<S: Solution>(b: &mut Bencher, input: &Input) {
+    let parsed = S::parse(input).unwrap();
+    b.iter(|| S::part2(black_box(&parsed)).unwrap())
+}
+
+struct VariantBench<S: Solution> {
+    input: Input,
+    variant: &'static Variant<S::Parsed>,
+}
+
+impl<S: Solution> TDynBenchFn for VariantBench<S> {
+    fn run(&self, b: &mut Bencher) {
+        let parsed = S::parse(&self.input).unwrap();
+        b.iter(|| (self.variant.solve)(black_box(&self.input), black_box(&parsed)).unwrap())
+    }
+}
+
+/// One benchmark per registered variant of `S`, for use as a group in
+/// `benchmark_main!`.
+pub fn variants<S: Solution + 'static>(input: &Input) -> Vec<TestDescAndFn> {
+    S::VARIANTS
+        .iter()
+        .map(|variant| TestDescAndFn {
+            desc: TestDesc {
+                name: TestName::from(format!("variant::part{}_{}", variant.part, variant.name)),
+                ignore: false,
+            },
+            testfn: TestFn::DynBenchFn(Box::new(VariantBench::<S> {
+                input: input.clone(),
+                variant,
+            })),
+        })
+        .collect()
+}
@@ -0,0 +1,173 @@
+use std::boxed::Box;
+use std::error;
+use std::fmt;
+use std::ops::Range;
+
+pub type Result<T> = ::std::result::Result<T, Box<dyn error::Error>>;
+
+/// Where in the input a parse error occurred.
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct Location {
+    /// Name of the input, usually its path.
+    pub path: String,
+    /// 1-based line number, 0 until the error has been placed on a line.
+    pub line: usize,
+    /// Byte range of the offending text within the line.
+    pub columns: Range<usize>,
+    /// The full source line.
+    pub text: String,
+}
+
+impl Location {
+    fn columns(columns: Range<usize>) -> Location {
+        Location {
+            columns,
+            ..Default::default()
+        }
+    }
+}
+
+/// A malformed line of puzzle input.
+///
+/// Line parsers create these with only the column span filled in, and the
+/// line reader then adds the path, line number and source text with
+/// [`ParseError::at_line`].
+#[derive(Debug, Clone, PartialEq)]
+pub enum ParseError {
+    /// The line does not have the expected shape.
+    Syntax { loc: Location, expected: String },
+    /// A numeric field could not be parsed.
+    Number { loc: Location, reason: String },
+    /// A field was parsed but its value is not allowed.
+    Value { loc: Location, reason: String },
+}
+
+impl ParseError {
+    pub fn syntax(columns: Range<usize>, expected: impl Into<String>) -> ParseError {
+        ParseError::Syntax {
+            loc: Location::columns(columns),
+            expected: expected.into(),
+        }
+    }
+
+    pub fn number(columns: Range<usize>, reason: impl ToString) -> ParseError {
+        ParseError::Number {
+            loc: Location::columns(columns),
+            reason: reason.to_string(),
+        }
+    }
+
+    pub fn value(columns: Range<usize>, reason: impl Into<String>) -> ParseError {
+        ParseError::Value {
+            loc: Location::columns(columns),
+            reason: reason.into(),
+        }
+    }
+
+    pub fn location(&self) -> &Location {
+        match self {
+            ParseError::Syntax { loc, .. }
+            | ParseError::Number { loc, .. }
+            | ParseError::Value { loc, .. } => loc,
+        }
+    }
+
+    fn location_mut(&mut self) -> &mut Location {
+        match self {
+            ParseError::Syntax { loc, .. }
+            | ParseError::Number { loc, .. }
+            | ParseError::Value { loc, .. } => loc,
+        }
+    }
+
+    /// Move the column span right by `offset` bytes, for errors found while
+    /// parsing a sub-slice of the line.
+    pub fn offset(mut self, offset: usize) -> ParseError {
+        let loc = self.location_mut();
+        loc.columns = loc.columns.start + offset..loc.columns.end + offset;
+        self
+    }
+
+    /// Place the error on line `line` (1-based) of the input `path`.
+    pub fn at_line(mut self, path: &str, line: usize, text: &str) -> ParseError {
+        let loc = self.location_mut();
+        loc.path = path.to_string();
+        loc.line = line;
+        loc.text = text.to_string();
+        self
+    }
+
+    fn message(&self) -> String {
+        match self {
+            ParseError::Syntax { expected, .. } => format!("expected {}", expected),
+            ParseError::Number { reason, .. } => format!("invalid number: {}", reason),
+            ParseError::Value { reason, .. } => format!("invalid value: {}", reason),
+        }
+    }
+}
+
+/// The byte range of `part` within `line`. `part` must be a sub-slice of
+/// `line`, as returned by `split`, `trim` and friends.
+pub fn span(line: &str, part: &str) -> Range<usize> {
+    let start = part.as_ptr() as usize - line.as_ptr() as usize;
+    debug_assert!(start + part.len() <= line.len());
+    start..start + part.len()
+}
+
+impl fmt::Display for ParseError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let loc = self.location();
+        writeln!(f, "parse error: {}", self.message())?;
+        if loc.line == 0 {
+            return write!(f, "  --> column {}", loc.columns.start + 1);
+        }
+        let start = loc
+            .text
+            .get(..loc.columns.start)
+            .map_or(0, |s| s.chars().count());
+        let width = loc
+            .text
+            .get(loc.columns.clone())
+            .map_or(0, |s| s.chars().count())
+            .max(1);
+        let gutter = " ".repeat(loc.line.to_string().len());
+        writeln!(f, "{}--> {}:{}:{}", gutter, loc.path, loc.line, start + 1)?;
+        writeln!(f, "{} |", gutter)?;
+        writeln!(f, "{} | {}", loc.line, loc.text)?;
+        write!(f, "{} | {}{}", gutter, " ".repeat(start), "^".repeat(width))
+    }
+}
+
+impl error::Error for ParseError {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn caret_diagnostic() {
+        let line = "#1 @ 1,3: 4xq";
+        let err = ParseError::number(12..13, "invalid digit found in string").at_line(
+            "claims.txt",
+            17,
+            line,
+        );
+        assert_eq!(
+            err.to_string(),
+            "parse error: invalid number: invalid digit found in string\n\
+             \x20 --> claims.txt:17:13\n\
+             \x20  |\n\
+             17 | #1 @ 1,3: 4xq\n\
+             \x20  |             ^"
+        );
+    }
+
+    #[test]
+    fn span_of_field() {
+        let line = "12, 345";
+        let field = line.split(',').nth(1).unwrap().trim();
+        assert_eq!(span(line, field), 4..7);
+        let err = ParseError::value(0..2, "too small").offset(4);
+        assert_eq!(err.location().columns, 4..6);
+    }
+}
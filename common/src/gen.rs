@@ -0,0 +1,142 @@
+//! Synthetic puzzle input, for stressing the solvers beyond the bundled
+//! inputs. Every day's generator comes with slow but obviously correct
+//! reference solvers that the real solutions are checked against.
+use crate::error::Result;
+use crate::input::Input;
+use crate::solution::{cross_check, Solution};
+
+/// Small seeded random number generator (SplitMix64). Not the `rand` crate,
+/// so that a seed gives the same input whatever version of it is around.
+#[derive(Debug, Clone)]
+pub struct Rng {
+    state: u64,
+}
+
+impl Rng {
+    pub fn new(seed: u64) -> Rng {
+        Rng { state: seed }
+    }
+
+    pub fn next_u64(&mut self) -> u64 {
+        self.state = self.state.wrapping_add(0x9e37_79b9_7f4a_7c15);
+        let mut z = self.state;
+        z = (z ^ (z >> 30)).wrapping_mul(0xbf58_476d_1ce4_e5b9);
+        z = (z ^ (z >> 27)).wrapping_mul(0x94d0_49bb_1331_11eb);
+        z ^ (z >> 31)
+    }
+
+    /// Uniform in `0..n`. `n` must not be 0.
+    pub fn below(&mut self, n: u64) -> u64 {
+        ((u128::from(self.next_u64()) * u128::from(n)) >> 64) as u64
+    }
+
+    /// Uniform in `lo..=hi`.
+    pub fn range(&mut self, lo: i64, hi: i64) -> i64 {
+        lo + self.below((hi - lo) as u64 + 1) as i64
+    }
+
+    /// True with probability `p`.
+    pub fn chance(&mut self, p: f64) -> bool {
+        (self.next_u64() >> 11) as f64 / (1u64 << 53) as f64 <= p
+    }
+
+    /// A uniformly chosen item. `items` must not be empty.
+    pub fn pick<'a, T>(&mut self, items: &'a [T]) -> &'a T {
+        assert!(!items.is_empty(), "Rng::pick from an empty slice");
+        &items[self.below(items.len() as u64) as usize]
+    }
+
+    pub fn shuffle<T>(&mut self, items: &mut [T]) {
+        for i in (1..items.len()).rev() {
+            items.swap(i, self.below(i as u64 + 1) as usize);
+        }
+    }
+}
+
+/// A [`Solution`] that can make up its own puzzle input.
+pub trait Generator: Solution {
+    /// Knobs for the shape of the input, beyond its size.
+    type Params: Default;
+
+    /// Input text with roughly `size` records: lines, IDs, claims, shifts,
+    /// units or coordinates depending on the day. The generated puzzle
+    /// always has an answer, and only one.
+    fn generate(rng: &mut Rng, size: usize, params: &Self::Params) -> String;
+
+    /// Slow reference solution of part 1.
+    fn oracle1(parsed: &Self::Parsed) -> Result<Self::Answer1>;
+    /// Slow reference solution of part 2.
+    fn oracle2(parsed: &Self::Parsed) -> Result<Self::Answer2>;
+}
+
+/// Generate input for `S` from `seed`.
+pub fn generate<S: Generator>(seed: u64, size: usize, params: &S::Params) -> Input {
+    let text = S::generate(&mut Rng::new(seed), size, params);
+    Input::text(&format!("day{}-{}-{}", S::DAY, seed, size), text)
+}
+
+/// Check both parts and every variant of `S` against the reference solvers.
+pub fn check<S: Generator>(input: &Input) -> Result<()> {
+    let parsed = S::parse(input)?;
+    let answers = [
+        (
+            S::part1(&parsed)?.to_string(),
+            S::oracle1(&parsed)?.to_string(),
+        ),
+        (
+            S::part2(&parsed)?.to_string(),
+            S::oracle2(&parsed)?.to_string(),
+        ),
+    ];
+    for (part, (answer, expected)) in answers.iter().enumerate() {
+        if answer != expected {
+            return Err(format!(
+                "Day {} part {} answered {} instead of {} on {}",
+                S::DAY,
+                part + 1,
+                answer,
+                expected,
+                input
+            )
+            .into());
+        }
+    }
+    cross_check::<S>(input)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn rng_is_reproducible() {
+        let mut a = Rng::new(42);
+        let mut b = Rng::new(42);
+        for _ in 0..100 {
+            assert_eq!(a.next_u64(), b.next_u64());
+        }
+        // reference values of SplitMix64
+        assert_eq!(Rng::new(0).next_u64(), 0xe220_a839_7b1d_cdaf);
+    }
+
+    #[test]
+    fn rng_ranges() {
+        let mut rng = Rng::new(7);
+        for _ in 0..1000 {
+            assert!(rng.below(10) < 10);
+            let n = rng.range(-3, 3);
+            assert!((-3..=3).contains(&n));
+        }
+        let mut items: Vec<u32> = (0..20).collect();
+        rng.shuffle(&mut items);
+        items.sort_unstable();
+        assert_eq!(items, (0..20).collect::<Vec<_>>());
+        assert!(items.contains(rng.pick(&items)));
+    }
+
+    #[test]
+    #[should_panic(expected = "empty slice")]
+    fn pick_from_nothing() {
+        Rng::new(7).pick::<u32>(&[]);
+    }
+}
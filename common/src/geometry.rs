@@ -0,0 +1,252 @@
+//! Points and rectangles on the integer plane, with `y` growing downwards
+//! like the puzzle maps, and the distance metrics the puzzles use.
+use std::fmt;
+use std::ops::{Add, Neg, Range, Sub};
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct Point {
+    pub x: isize,
+    pub y: isize,
+}
+
+impl Point {
+    pub const ORIGIN: Point = Point { x: 0, y: 0 };
+
+    pub const fn new(x: isize, y: isize) -> Point {
+        Point { x, y }
+    }
+
+    /// Distance moving along the axes, one step at a time.
+    pub fn manhattan(self, other: Point) -> isize {
+        (self.x - other.x).abs() + (self.y - other.y).abs()
+    }
+
+    /// Distance moving diagonally as well, like a king in chess.
+    pub fn chebyshev(self, other: Point) -> isize {
+        (self.x - other.x).abs().max((self.y - other.y).abs())
+    }
+
+    /// Distance in a straight line.
+    pub fn euclidean(self, other: Point) -> f64 {
+        let (dx, dy) = ((self.x - other.x) as f64, (self.y - other.y) as f64);
+        dx.hypot(dy)
+    }
+}
+
+impl From<(isize, isize)> for Point {
+    fn from((x, y): (isize, isize)) -> Point {
+        Point { x, y }
+    }
+}
+
+impl Add for Point {
+    type Output = Point;
+
+    fn add(self, other: Point) -> Point {
+        Point::new(self.x + other.x, self.y + other.y)
+    }
+}
+
+impl Sub for Point {
+    type Output = Point;
+
+    fn sub(self, other: Point) -> Point {
+        Point::new(self.x - other.x, self.y - other.y)
+    }
+}
+
+impl Neg for Point {
+    type Output = Point;
+
+    fn neg(self) -> Point {
+        Point::new(-self.x, -self.y)
+    }
+}
+
+impl fmt::Display for Point {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{},{}", self.x, self.y)
+    }
+}
+
+/// `width` by `height` cells from the top left one at `x`, `y`. The cells
+/// on the right and bottom edges are `right() - 1` and `bottom() - 1`.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
+pub struct Rect {
+    pub x: isize,
+    pub y: isize,
+    pub width: usize,
+    pub height: usize,
+}
+
+impl Rect {
+    pub const fn new(x: isize, y: isize, width: usize, height: usize) -> Rect {
+        Rect {
+            x,
+            y,
+            width,
+            height,
+        }
+    }
+
+    /// The rectangle from `min` to `max`, both included.
+    pub fn from_corners(min: Point, max: Point) -> Rect {
+        let size = |lo: isize, hi: isize| if hi < lo { 0 } else { (hi - lo) as usize + 1 };
+        Rect::new(min.x, min.y, size(min.x, max.x), size(min.y, max.y))
+    }
+
+    /// The smallest rectangle with all of `points`, `None` without any.
+    pub fn bounding_box(points: impl IntoIterator<Item = Point>) -> Option<Rect> {
+        let mut points = points.into_iter();
+        let first = points.next()?;
+        let (min, max) = points.fold((first, first), |(min, max), p| {
+            let min = Point::new(min.x.min(p.x), min.y.min(p.y));
+            (min, Point::new(max.x.max(p.x), max.y.max(p.y)))
+        });
+        Some(Rect::from_corners(min, max))
+    }
+
+    /// One past the rightmost column.
+    pub fn right(&self) -> isize {
+        self.x + self.width as isize
+    }
+
+    /// One past the bottom row.
+    pub fn bottom(&self) -> isize {
+        self.y + self.height as isize
+    }
+
+    pub fn min(&self) -> Point {
+        Point::new(self.x, self.y)
+    }
+
+    /// The bottom right cell. Meaningless for an empty rectangle.
+    pub fn max(&self) -> Point {
+        Point::new(self.right() - 1, self.bottom() - 1)
+    }
+
+    pub fn xs(&self) -> Range<isize> {
+        self.x..self.right()
+    }
+
+    pub fn ys(&self) -> Range<isize> {
+        self.y..self.bottom()
+    }
+
+    /// Number of cells, saturating rather than overflowing.
+    pub fn area(&self) -> usize {
+        self.width.saturating_mul(self.height)
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.width == 0 || self.height == 0
+    }
+
+    pub fn contains(&self, p: Point) -> bool {
+        self.xs().contains(&p.x) && self.ys().contains(&p.y)
+    }
+
+    /// Whether `p` is one of the outermost cells.
+    pub fn on_edge(&self, p: Point) -> bool {
+        let max = self.max();
+        self.contains(p) && (p.x == self.x || p.x == max.x || p.y == self.y || p.y == max.y)
+    }
+
+    /// The cells in both rectangles, `None` if there are none.
+    pub fn intersection(&self, other: &Rect) -> Option<Rect> {
+        let min = Point::new(self.x.max(other.x), self.y.max(other.y));
+        let max = Point::new(
+            self.max().x.min(other.max().x),
+            self.max().y.min(other.max().y),
+        );
+        Some(Rect::from_corners(min, max)).filter(|r| !r.is_empty())
+    }
+
+    /// The smallest rectangle with both rectangles in it. Empty rectangles
+    /// have no cells, so they are left out.
+    pub fn union(&self, other: &Rect) -> Rect {
+        match (self.is_empty(), other.is_empty()) {
+            (true, _) => *other,
+            (false, true) => *self,
+            (false, false) => {
+                Rect::bounding_box(vec![self.min(), self.max(), other.min(), other.max()]).unwrap()
+            }
+        }
+    }
+
+    /// The rectangle with `margin` more cells on every side, or fewer when
+    /// negative.
+    pub fn grow(&self, margin: isize) -> Rect {
+        let min = Point::new(self.x - margin, self.y - margin);
+        Rect::from_corners(min, self.max() + Point::new(margin, margin))
+    }
+
+    /// The cells, row by row.
+    pub fn points(&self) -> impl Iterator<Item = Point> {
+        let xs = self.xs();
+        self.ys()
+            .flat_map(move |y| xs.clone().map(move |x| Point::new(x, y)))
+    }
+}
+
+impl fmt::Display for Rect {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}x{} at {}", self.width, self.height, self.min())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn metrics() {
+        let (a, b) = (Point::new(1, -2), Point::new(-3, 1));
+        assert_eq!(a.manhattan(b), 7);
+        assert_eq!(b.manhattan(a), 7);
+        assert_eq!(a.chebyshev(b), 4);
+        assert_eq!(a.euclidean(b), 5.0);
+        assert_eq!(a - b, Point::new(4, -3));
+        assert_eq!(a + -a, Point::ORIGIN);
+        assert_eq!(Point::from((2, 3)), Point::new(2, 3));
+    }
+
+    #[test]
+    fn corners_and_edges() {
+        let r = Rect::new(-1, 2, 3, 2);
+        assert_eq!((r.right(), r.bottom()), (2, 4));
+        assert_eq!(r.max(), Point::new(1, 3));
+        assert_eq!(Rect::from_corners(r.min(), r.max()), r);
+        assert_eq!(r.area(), 6);
+        assert!(r.contains(Point::new(1, 3)));
+        assert!(!r.contains(Point::new(2, 3)));
+        assert!(r.on_edge(Point::new(0, 2)));
+        assert!(!Rect::new(0, 0, 3, 3).on_edge(Point::new(1, 1)));
+        assert_eq!(r.points().count(), 6);
+        assert_eq!(r.points().nth(3), Some(Point::new(-1, 3)));
+        assert_eq!(Rect::new(0, 0, usize::MAX, 2).area(), usize::MAX);
+        assert_eq!(r.to_string(), "3x2 at -1,2");
+    }
+
+    #[test]
+    fn intersection_and_union() {
+        let a = Rect::new(1, 3, 4, 4);
+        let b = Rect::new(3, 1, 4, 4);
+        assert_eq!(a.intersection(&b), Some(Rect::new(3, 3, 2, 2)));
+        assert_eq!(a.intersection(&Rect::new(5, 5, 2, 2)), None);
+        assert_eq!(a.intersection(&Rect::new(2, 4, 0, 9)), None);
+        assert_eq!(a.union(&b), Rect::new(1, 1, 6, 6));
+        assert_eq!(a.union(&Rect::new(-9, -9, 0, 0)), a);
+        assert_eq!(Rect::default().union(&a), a);
+    }
+
+    #[test]
+    fn bounding_boxes() {
+        let points = vec![Point::new(3, -1), Point::new(-2, 4), Point::new(0, 0)];
+        let bounds = Rect::bounding_box(points).unwrap();
+        assert_eq!(bounds, Rect::new(-2, -1, 6, 6));
+        assert_eq!(bounds.grow(1), Rect::new(-3, -2, 8, 8));
+        assert_eq!(bounds.grow(-3), Rect::new(1, 2, 0, 0));
+        assert_eq!(Rect::bounding_box(Vec::new()), None);
+    }
+}
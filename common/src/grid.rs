@@ -0,0 +1,460 @@
+//! Rectangles of cells addressed by `x` and `y`, for the puzzles that play
+//! out on a map. The top left cell can be anywhere, negative coordinates
+//! included, and `y` grows downwards. Rows are stored one after the other,
+//! so going through the cells row by row is fastest.
+use std::ops::{Index, IndexMut, Range};
+
+use crate::error::Result;
+use crate::geometry::{Point, Rect};
+use crate::par;
+
+/// Largest grid the solvers are willing to compute, in cells. The puzzles
+/// give positions and sizes as 32 bit numbers, so areas, distances and sums
+/// of them fit in an `isize` for the check against this.
+pub const MAX_CELLS: usize = 1 << 24;
+
+/// An error naming `what` would be laid out over `bounds` if it has more
+/// than [`MAX_CELLS`] cells.
+pub fn check_size(bounds: Rect, what: &str) -> Result<()> {
+    if bounds.area() > MAX_CELLS {
+        let size = format!("{}x{}", bounds.width, bounds.height);
+        return Err(format!("The {} is too large: {}", what, size).into());
+    }
+    Ok(())
+}
+
+/// Most cell visits a solver is willing to make, over all its passes. A grid
+/// within [`MAX_CELLS`] can still take hours if every cell looks at every
+/// one of thousands of coordinates.
+pub const MAX_WORK: usize = 1 << 28;
+
+/// An error naming `what` if it takes more than [`MAX_WORK`] cell visits.
+pub fn check_work(visits: usize, what: &str) -> Result<()> {
+    if visits > MAX_WORK {
+        let message = format!(
+            "The {} takes too much work: {} cell visits, at most {} are allowed",
+            what, visits, MAX_WORK
+        );
+        return Err(message.into());
+    }
+    Ok(())
+}
+
+/// Offsets of the neighbours up, left, right and down.
+const NEIGHBOURS4: [Point; 4] = [
+    Point::new(0, -1),
+    Point::new(-1, 0),
+    Point::new(1, 0),
+    Point::new(0, 1),
+];
+/// Offsets of all eight neighbours, in reading order.
+const NEIGHBOURS8: [Point; 8] = [
+    Point::new(-1, -1),
+    Point::new(0, -1),
+    Point::new(1, -1),
+    Point::new(-1, 0),
+    Point::new(1, 0),
+    Point::new(-1, 1),
+    Point::new(0, 1),
+    Point::new(1, 1),
+];
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Grid<T> {
+    bounds: Rect,
+    cells: Vec<T>,
+}
+
+impl<T: Clone> Grid<T> {
+    /// The cells of `bounds`, each holding `value`.
+    pub fn new(bounds: Rect, value: T) -> Grid<T> {
+        Grid {
+            bounds,
+            cells: vec![value; bounds.area()],
+        }
+    }
+}
+
+impl<T> Grid<T> {
+    /// The cells of `bounds`, with `f` of its position in each, evaluated
+    /// row by row.
+    pub fn from_fn<F>(bounds: Rect, f: F) -> Grid<T>
+    where
+        F: FnMut(Point) -> T,
+    {
+        Grid {
+            bounds,
+            cells: bounds.points().map(f).collect(),
+        }
+    }
+
+    /// The cells covered, wherever the grid starts.
+    pub fn bounds(&self) -> Rect {
+        self.bounds
+    }
+
+    pub fn width(&self) -> usize {
+        self.bounds.width
+    }
+
+    pub fn height(&self) -> usize {
+        self.bounds.height
+    }
+
+    /// `x` of the columns, left to right.
+    pub fn xs(&self) -> Range<isize> {
+        self.bounds.xs()
+    }
+
+    /// `y` of the rows, top to bottom.
+    pub fn ys(&self) -> Range<isize> {
+        self.bounds.ys()
+    }
+
+    pub fn contains(&self, p: Point) -> bool {
+        self.bounds.contains(p)
+    }
+
+    /// Whether `p` is one of the outermost cells.
+    pub fn on_edge(&self, p: Point) -> bool {
+        self.bounds.on_edge(p)
+    }
+
+    fn offset(&self, p: Point) -> Option<usize> {
+        if self.contains(p) {
+            let d = p - self.bounds.min();
+            Some(d.y as usize * self.width() + d.x as usize)
+        } else {
+            None
+        }
+    }
+
+    fn offset_or_panic(&self, p: Point) -> usize {
+        self.offset(p).unwrap_or_else(|| {
+            panic!(
+                "({}, {}) is outside the grid, x in {:?} and y in {:?}",
+                p.x,
+                p.y,
+                self.xs(),
+                self.ys()
+            )
+        })
+    }
+
+    pub fn get(&self, p: Point) -> Option<&T> {
+        self.offset(p).map(|i| &self.cells[i])
+    }
+
+    pub fn get_mut(&mut self, p: Point) -> Option<&mut T> {
+        self.offset(p).map(move |i| &mut self.cells[i])
+    }
+
+    /// The cells of row `y`, left to right.
+    pub fn row(&self, y: isize) -> &[T] {
+        let start = self.offset_or_panic(Point::new(self.bounds.x, y));
+        &self.cells[start..start + self.width()]
+    }
+
+    pub fn rows(&self) -> impl Iterator<Item = &[T]> {
+        // an empty grid has no cells to chunk, whatever its width
+        self.cells.chunks(self.width().max(1))
+    }
+
+    /// The cells of column `x`, top to bottom.
+    pub fn column(&self, x: isize) -> impl Iterator<Item = &T> {
+        let start = self.offset_or_panic(Point::new(x, self.bounds.y));
+        self.cells[start..].iter().step_by(self.width())
+    }
+
+    /// The cells in row order.
+    pub fn values(&self) -> std::slice::Iter<'_, T> {
+        self.cells.iter()
+    }
+
+    pub fn values_mut(&mut self) -> std::slice::IterMut<'_, T> {
+        self.cells.iter_mut()
+    }
+
+    /// The cells with their positions, in row order.
+    pub fn iter(&self) -> impl Iterator<Item = (Point, &T)> {
+        self.bounds.points().zip(self.cells.iter())
+    }
+
+    /// The cells of `rect`, which must all be inside.
+    pub fn view(&self, rect: Rect) -> View<'_, T> {
+        let start = self.checked_view(rect);
+        View {
+            cells: &self.cells[start..],
+            stride: self.width(),
+            width: rect.width,
+            height: rect.height,
+        }
+    }
+
+    pub fn view_mut(&mut self, rect: Rect) -> ViewMut<'_, T> {
+        let start = self.checked_view(rect);
+        ViewMut {
+            cells: &mut self.cells[start..],
+            stride: self.bounds.width,
+            width: rect.width,
+            height: rect.height,
+        }
+    }
+
+    /// Offset of the top left cell of a view, after checking that all of it
+    /// is inside.
+    fn checked_view(&self, rect: Rect) -> usize {
+        if rect.is_empty() {
+            return 0;
+        }
+        self.offset_or_panic(rect.max());
+        self.offset_or_panic(rect.min())
+    }
+
+    /// The neighbours of `p` up, left, right and down, as far as they are
+    /// inside.
+    pub fn neighbours4(&self, p: Point) -> impl Iterator<Item = Point> + '_ {
+        self.neighbours(p, &NEIGHBOURS4)
+    }
+
+    /// All eight neighbours of `p` that are inside, in reading order.
+    pub fn neighbours8(&self, p: Point) -> impl Iterator<Item = Point> + '_ {
+        self.neighbours(p, &NEIGHBOURS8)
+    }
+
+    fn neighbours<'a>(
+        &'a self,
+        p: Point,
+        offsets: &'static [Point],
+    ) -> impl Iterator<Item = Point> + 'a {
+        offsets
+            .iter()
+            .map(move |&d| p + d)
+            .filter(move |&n| self.contains(n))
+    }
+
+    /// A grid of the same shape with `f` applied to every cell.
+    pub fn map<U>(&self, f: impl FnMut(&T) -> U) -> Grid<U> {
+        Grid {
+            bounds: self.bounds,
+            cells: self.cells.iter().map(f).collect(),
+        }
+    }
+
+    /// Combine the cells and their positions into one value, in row order.
+    pub fn fold<A>(&self, init: A, mut f: impl FnMut(A, Point, &T) -> A) -> A {
+        self.iter().fold(init, |acc, (p, cell)| f(acc, p, cell))
+    }
+}
+
+impl<T: Default + Send> Grid<T> {
+    /// Like [`Grid::from_fn`], with the rows spread over all cores when the
+    /// `parallel` feature is on.
+    pub fn par_from_fn<F>(bounds: Rect, f: F) -> Grid<T>
+    where
+        F: Fn(Point) -> T + Sync + Send,
+    {
+        let mut cells = Vec::with_capacity(bounds.area());
+        cells.resize_with(bounds.area(), Default::default);
+        par::for_each_chunk_mut(&mut cells, bounds.width.max(1), |row, cells| {
+            let y = bounds.y + row as isize;
+            for (x, cell) in bounds.xs().zip(cells.iter_mut()) {
+                *cell = f(Point::new(x, y));
+            }
+        });
+        Grid { bounds, cells }
+    }
+}
+
+impl<T> Index<Point> for Grid<T> {
+    type Output = T;
+
+    fn index(&self, p: Point) -> &T {
+        &self.cells[self.offset_or_panic(p)]
+    }
+}
+
+impl<T> IndexMut<Point> for Grid<T> {
+    fn index_mut(&mut self, p: Point) -> &mut T {
+        let i = self.offset_or_panic(p);
+        &mut self.cells[i]
+    }
+}
+
+/// A rectangle of cells inside a [`Grid`].
+pub struct View<'a, T> {
+    /// The cells from the top left one of the view to the end of the grid.
+    cells: &'a [T],
+    stride: usize,
+    width: usize,
+    height: usize,
+}
+
+impl<'a, T> View<'a, T> {
+    pub fn width(&self) -> usize {
+        self.width
+    }
+
+    pub fn height(&self) -> usize {
+        self.height
+    }
+
+    pub fn rows(&self) -> impl Iterator<Item = &'a [T]> {
+        let (cells, stride, width) = (self.cells, self.stride, self.width);
+        (0..self.height).map(move |row| &cells[row * stride..row * stride + width])
+    }
+
+    /// The cells in row order.
+    pub fn iter(&self) -> impl Iterator<Item = &'a T> {
+        self.rows().flatten()
+    }
+}
+
+/// A rectangle of cells inside a [`Grid`] that can be changed.
+pub struct ViewMut<'a, T> {
+    cells: &'a mut [T],
+    stride: usize,
+    width: usize,
+    height: usize,
+}
+
+impl<T> ViewMut<'_, T> {
+    pub fn width(&self) -> usize {
+        self.width
+    }
+
+    pub fn height(&self) -> usize {
+        self.height
+    }
+
+    /// The cells in row order.
+    pub fn iter_mut(&mut self) -> impl Iterator<Item = &mut T> {
+        let width = self.width;
+        self.cells
+            .chunks_mut(self.stride.max(1))
+            .take(self.height)
+            .flat_map(move |row| &mut row[..width])
+    }
+
+    pub fn fill(&mut self, value: T)
+    where
+        T: Clone,
+    {
+        self.iter_mut().for_each(|cell| *cell = value.clone());
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn p(x: isize, y: isize) -> Point {
+        Point::new(x, y)
+    }
+
+    /// 4 by 3 cells from (-1, -2), each holding `10 * x + y`.
+    fn example() -> Grid<isize> {
+        Grid::from_fn(Rect::new(-1, -2, 4, 3), |p| 10 * p.x + p.y)
+    }
+
+    #[test]
+    fn addressing() {
+        let mut grid = example();
+        assert_eq!((grid.width(), grid.height()), (4, 3));
+        assert_eq!((grid.xs(), grid.ys()), (-1..3, -2..1));
+        assert_eq!(grid[p(-1, -2)], -12);
+        assert_eq!(grid[p(2, 0)], 20);
+        assert_eq!(grid.get(p(3, 0)), None);
+        assert_eq!(grid.get(p(0, 1)), None);
+        grid[p(0, 0)] = 99;
+        *grid.get_mut(p(1, 0)).unwrap() += 1;
+        assert_eq!(grid.row(0), &[-10, 99, 11, 20]);
+        assert!(grid.on_edge(p(-1, -1)));
+        assert!(grid.on_edge(p(1, 0)));
+        assert!(!grid.on_edge(p(1, -1)));
+        assert!(!grid.on_edge(p(5, 0)));
+    }
+
+    #[test]
+    #[should_panic(expected = "(3, 0) is outside the grid")]
+    fn index_outside() {
+        let _ = example()[p(3, 0)];
+    }
+
+    #[test]
+    fn rows_and_columns() {
+        let grid = example();
+        let rows: Vec<&[isize]> = grid.rows().collect();
+        assert_eq!(rows, [[-12, -2, 8, 18], [-11, -1, 9, 19], [-10, 0, 10, 20]]);
+        assert_eq!(grid.column(1).copied().collect::<Vec<_>>(), [8, 9, 10]);
+        assert_eq!(grid.iter().nth(5), Some((p(0, -1), &-1)));
+        assert_eq!(grid.values().sum::<isize>(), 48);
+        assert_eq!(Grid::new(Rect::default(), 0u8).rows().count(), 0);
+    }
+
+    #[test]
+    fn views() {
+        let mut grid = example();
+        let view = grid.view(Rect::new(0, -1, 2, 2));
+        assert_eq!((view.width(), view.height()), (2, 2));
+        assert_eq!(view.iter().copied().collect::<Vec<_>>(), [-1, 9, 0, 10]);
+        grid.view_mut(Rect::new(1, -2, 2, 3)).fill(7);
+        assert_eq!(grid.row(-1), &[-11, -1, 7, 7]);
+        grid.view_mut(Rect::new(-1, 0, 4, 1))
+            .iter_mut()
+            .for_each(|c| *c = -*c);
+        assert_eq!(grid.row(0), &[10, 0, -7, -7]);
+        assert_eq!(grid.view(Rect::new(2, 0, 0, 0)).iter().count(), 0);
+    }
+
+    #[test]
+    #[should_panic(expected = "outside the grid")]
+    fn view_outside() {
+        example().view(Rect::new(1, -1, 3, 1));
+    }
+
+    #[test]
+    fn neighbours() {
+        let grid = example();
+        let corner: Vec<_> = grid.neighbours4(p(-1, -2)).collect();
+        assert_eq!(corner, [p(0, -2), p(-1, -1)]);
+        assert_eq!(grid.neighbours4(p(0, -1)).count(), 4);
+        assert_eq!(grid.neighbours8(p(0, -1)).count(), 8);
+        let edge: Vec<_> = grid.neighbours8(p(2, 0)).collect();
+        assert_eq!(edge, [p(1, -1), p(2, -1), p(1, 0)]);
+    }
+
+    #[test]
+    fn map_and_fold() {
+        let grid = example();
+        let signs = grid.map(|&c| c.signum());
+        assert_eq!(signs.row(-2), &[-1, -1, 1, 1]);
+        assert_eq!(signs.bounds(), grid.bounds());
+        let on_diagonal = grid.fold(0, |n, p, _| n + (p.x == p.y) as usize);
+        assert_eq!(on_diagonal, 2);
+    }
+
+    #[test]
+    fn parallel_matches_sequential() {
+        let bounds = Rect::new(-50, 7, 123, 45);
+        let f = |p: Point| p.x * p.x - 3 * p.y;
+        let grid = Grid::par_from_fn(bounds, f);
+        assert_eq!(grid, Grid::from_fn(bounds, f));
+        assert_eq!(grid, par::sequential(|| Grid::par_from_fn(bounds, f)));
+    }
+
+    #[test]
+    fn size_limit() {
+        assert!(check_size(Rect::new(-5, -5, 4096, 4096), "map").is_ok());
+        let err = check_size(Rect::new(0, 0, 4097, 4096), "map").unwrap_err();
+        assert_eq!(err.to_string(), "The map is too large: 4097x4096");
+        assert!(check_size(Rect::new(0, 0, usize::MAX, 2), "map").is_err());
+    }
+
+    #[test]
+    fn work_limit() {
+        assert!(check_work(MAX_WORK, "map").is_ok());
+        let err = check_work(MAX_WORK + 1, "map").unwrap_err();
+        assert!(err.to_string().starts_with("The map takes too much work"));
+    }
+}
@@ -0,0 +1,371 @@
+use std::env;
+use std::fmt;
+use std::fs::{self, File};
+use std::io::{self, BufRead, BufReader, Cursor, Read};
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicBool, Ordering};
+
+use crate::error::{ParseError, Result};
+use crate::parse::FromLine;
+
+/// Environment variable naming a directory with one `dayN.txt` file per day.
+pub const INPUT_DIR_VAR: &str = "AOC_INPUT_DIR";
+
+/// What to do with blank lines at the end of an input.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TrailingBlanks {
+    /// Leave them out, as editors and downloads tend to add them.
+    Skip,
+    /// Report the first one as a parse error.
+    Reject,
+}
+
+static REJECT_TRAILING_BLANKS: AtomicBool = AtomicBool::new(false);
+
+/// Set what [`read_lines`] and [`Input::open`] do with blank lines at the
+/// end of an input, for the whole process. They are skipped by default.
+pub fn set_trailing_blanks(blanks: TrailingBlanks) {
+    REJECT_TRAILING_BLANKS.store(blanks == TrailingBlanks::Reject, Ordering::Relaxed);
+}
+
+/// What the readers currently do with blank lines at the end of an input.
+pub fn trailing_blanks() -> TrailingBlanks {
+    match REJECT_TRAILING_BLANKS.load(Ordering::Relaxed) {
+        true => TrailingBlanks::Reject,
+        false => TrailingBlanks::Skip,
+    }
+}
+
+/// Puzzle input, either a file on disk or text that is already in memory.
+#[derive(Debug, Clone)]
+pub enum Input {
+    File(PathBuf),
+    Text { name: String, text: String },
+}
+
+impl Input {
+    pub fn file(path: impl Into<PathBuf>) -> Input {
+        Input::File(path.into())
+    }
+
+    pub fn text(name: &str, text: impl Into<String>) -> Input {
+        Input::Text {
+            name: name.to_string(),
+            text: text.into(),
+        }
+    }
+
+    /// Read all of standard input. It can only be consumed once, so it is
+    /// kept in memory and can then be opened any number of times.
+    pub fn stdin() -> Result<Input> {
+        let mut text = String::new();
+        io::stdin()
+            .read_to_string(&mut text)
+            .map_err(|e| format!("Unable to read input from stdin: {}", e))?;
+        Ok(Input::text("<stdin>", text))
+    }
+
+    /// A stable hash of the normalized input, used to key known answers. A
+    /// copy with a BOM, other line endings or blank lines at the end has
+    /// the same answers, and so the same hash.
+    pub fn hash(&self) -> Result<String> {
+        let text = self.normalized(TrailingBlanks::Skip)?;
+        Ok(format!("{:016x}", fnv1a(text.as_bytes())))
+    }
+
+    fn open_raw(&self) -> Result<Box<dyn BufRead + '_>> {
+        match self {
+            Input::File(path) => {
+                let f = File::open(path).map_err(|e| {
+                    format!("Unable to open input file '{}': {}", path.display(), e)
+                })?;
+                Ok(Box::new(BufReader::new(f)))
+            }
+            Input::Text { text, .. } => Ok(Box::new(text.as_bytes())),
+        }
+    }
+
+    /// The text of the input without a byte order mark, with `\n` line
+    /// endings, without whitespace at the end of the lines and, depending on
+    /// `blanks`, without blank lines at the end.
+    pub fn normalized(&self, blanks: TrailingBlanks) -> Result<String> {
+        let mut bytes = Vec::new();
+        self.open_raw()?.read_to_end(&mut bytes)?;
+        let text = String::from_utf8(bytes)
+            .map_err(|e| format!("Input '{}' is not valid UTF-8: {}", self, e.utf8_error()))?;
+        Ok(normalize(&self.to_string(), &text, blanks)?)
+    }
+
+    /// Open the normalized input for buffered reading, with blank lines at
+    /// the end handled as set with [`set_trailing_blanks`].
+    pub fn open(&self) -> Result<Box<dyn BufRead + '_>> {
+        let text = self.normalized(trailing_blanks())?;
+        Ok(Box::new(Cursor::new(text.into_bytes())))
+    }
+
+    /// The lines of the normalized input, with a warning about characters
+    /// that don't belong in puzzle input.
+    pub fn lines(&self, blanks: TrailingBlanks) -> Result<Vec<String>> {
+        let text = self.normalized(blanks)?;
+        if let Some((line, column, c, count)) = suspicious(&text) {
+            let more = match count {
+                1 => String::new(),
+                _ => format!(", and {} more", count - 1),
+            };
+            log::warn!(
+                "{}:{}:{}: suspicious character {:?} (U+{:04X}){}",
+                self,
+                line,
+                column,
+                c,
+                c as u32,
+                more
+            );
+        }
+        Ok(text.lines().map(str::to_string).collect())
+    }
+}
+
+impl fmt::Display for Input {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Input::File(path) => write!(f, "{}", path.display()),
+            Input::Text { name, .. } => write!(f, "{}", name),
+        }
+    }
+}
+
+// 64-bit FNV-1a, which unlike `DefaultHasher` is the same on every build.
+fn fnv1a(bytes: &[u8]) -> u64 {
+    bytes.iter().fold(0xcbf2_9ce4_8422_2325, |hash, &b| {
+        (hash ^ u64::from(b)).wrapping_mul(0x0000_0100_0000_01b3)
+    })
+}
+
+fn normalize(
+    name: &str,
+    text: &str,
+    blanks: TrailingBlanks,
+) -> std::result::Result<String, ParseError> {
+    let text = text.strip_prefix('\u{feff}').unwrap_or(text);
+    let text = text.replace("\r\n", "\n").replace('\r', "\n");
+    let mut lines: Vec<&str> = text.lines().map(str::trim_end).collect();
+    let content = lines
+        .iter()
+        .rposition(|line| !line.is_empty())
+        .map_or(0, |i| i + 1);
+    if content < lines.len() && blanks == TrailingBlanks::Reject {
+        let err = ParseError::syntax(0..0, "no blank lines at the end of the input");
+        return Err(err.at_line(name, content + 1, ""));
+    }
+    lines.truncate(content);
+    Ok(lines.iter().map(|line| format!("{}\n", line)).collect())
+}
+
+/// Line and column, both 1-based, of the first control or non-ASCII
+/// character, which puzzle input doesn't have, and how many there are.
+fn suspicious(text: &str) -> Option<(usize, usize, char, usize)> {
+    let is_suspicious = |c: char| !c.is_ascii() || (c.is_control() && c != '\t' && c != '\n');
+    let count = text.chars().filter(|&c| is_suspicious(c)).count();
+    text.lines().enumerate().find_map(|(index, line)| {
+        let (column, c) = line.chars().enumerate().find(|&(_, c)| is_suspicious(c))?;
+        Some((index + 1, column + 1, c, count))
+    })
+}
+
+/// Pick the input for `day`. In order of precedence: `arg` (a path, or `-`
+/// for stdin), `$AOC_INPUT_DIR/dayN.txt`, and finally the day's `default`.
+pub fn resolve_input(day: u32, arg: Option<&str>, default: &Path) -> Result<Input> {
+    let input_dir = env::var_os(INPUT_DIR_VAR).map(PathBuf::from);
+    resolve_input_from(day, arg, input_dir.as_deref(), default)
+}
+
+fn resolve_input_from(
+    day: u32,
+    arg: Option<&str>,
+    input_dir: Option<&Path>,
+    default: &Path,
+) -> Result<Input> {
+    let (path, origin) = match (arg, input_dir) {
+        (Some("-"), _) => return Input::stdin(),
+        (Some(arg), _) => (PathBuf::from(arg), "given with --input".to_string()),
+        (None, Some(dir)) => (
+            dir.join(format!("day{}.txt", day)),
+            format!("from {}", INPUT_DIR_VAR),
+        ),
+        (None, None) => (default.to_path_buf(), "default".to_string()),
+    };
+    if !path.is_file() {
+        return Err(format!(
+            "Input file '{}' for day {} ({}) does not exist. \
+             Use --input FILE, --input - to read stdin, or set {} to a directory with dayN.txt files",
+            path.display(),
+            day,
+            origin,
+            INPUT_DIR_VAR
+        )
+        .into());
+    }
+    Ok(Input::File(path))
+}
+
+/// All `*.txt` inputs in `dir`, sorted by name.
+pub fn list_inputs(dir: &Path) -> Result<Vec<Input>> {
+    let entries = fs::read_dir(dir)
+        .map_err(|e| format!("Unable to list inputs in '{}': {}", dir.display(), e))?;
+    let mut paths = Vec::new();
+    for entry in entries {
+        let path = entry?.path();
+        if path.is_file() && path.extension().is_some_and(|ext| ext == "txt") {
+            paths.push(path);
+        }
+    }
+    paths.sort();
+    Ok(paths.into_iter().map(Input::File).collect())
+}
+
+/// Read all lines of an input, normalized and with blank lines at the end
+/// handled as set with [`set_trailing_blanks`].
+pub fn read_lines(input: &Input) -> Result<Vec<String>> {
+    input.lines(trailing_blanks())
+}
+
+/// Parse every line of an input with `parse`, stopping at the first error.
+/// Parse errors are placed on the line they occurred on.
+pub fn parse_lines<T, F>(input: &Input, mut parse: F) -> Result<Vec<T>>
+where
+    F: FnMut(&str) -> std::result::Result<T, ParseError>,
+{
+    let name = input.to_string();
+    let mut items = Vec::new();
+    for (index, line) in read_lines(input)?.into_iter().enumerate() {
+        let item = parse(&line).map_err(|e| e.at_line(&name, index + 1, &line))?;
+        items.push(item);
+    }
+    Ok(items)
+}
+
+/// Read a record from every line of an input.
+pub fn read_records<T: FromLine>(input: &Input) -> Result<Vec<T>> {
+    parse_lines(input, T::from_line)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const DEFAULT: &str = concat!(env!("CARGO_MANIFEST_DIR"), "/Cargo.toml");
+
+    fn resolved_path(input: Input) -> PathBuf {
+        match input {
+            Input::File(path) => path,
+            Input::Text { .. } => panic!("expected a file input"),
+        }
+    }
+
+    #[test]
+    fn default_input() {
+        let input = resolve_input_from(1, None, None, Path::new(DEFAULT)).unwrap();
+        assert_eq!(resolved_path(input), Path::new(DEFAULT));
+    }
+
+    #[test]
+    fn explicit_input_wins() {
+        let dir = Path::new(env!("CARGO_MANIFEST_DIR"));
+        let input = resolve_input_from(1, Some(DEFAULT), Some(dir), Path::new("nope")).unwrap();
+        assert_eq!(resolved_path(input), Path::new(DEFAULT));
+    }
+
+    #[test]
+    fn input_dir() {
+        let dir = Path::new(env!("CARGO_MANIFEST_DIR"));
+        let err = resolve_input_from(7, None, Some(dir), Path::new(DEFAULT)).unwrap_err();
+        let msg = err.to_string();
+        assert!(msg.contains("day7.txt"), "{}", msg);
+        assert!(msg.contains(INPUT_DIR_VAR), "{}", msg);
+    }
+
+    #[test]
+    fn missing_file() {
+        let err =
+            resolve_input_from(3, Some("no/such/file.txt"), None, Path::new(DEFAULT)).unwrap_err();
+        assert!(err.to_string().contains("'no/such/file.txt' for day 3"));
+    }
+
+    #[test]
+    fn parse_error_line() {
+        let input = Input::text("numbers", "1\n2\nx3\n");
+        let err = parse_lines(&input, |line| {
+            line.parse::<i32>()
+                .map_err(|e| ParseError::number(0..line.len(), e))
+        })
+        .unwrap_err();
+        let err = err.downcast::<ParseError>().unwrap();
+        assert_eq!(err.location().path, "numbers");
+        assert_eq!(err.location().line, 3);
+        assert_eq!(err.location().text, "x3");
+    }
+
+    #[test]
+    fn input_hash() {
+        assert_eq!(fnv1a(b""), 0xcbf2_9ce4_8422_2325);
+        assert_eq!(fnv1a(b"a"), 0xaf63_dc4c_8601_ec8c);
+        let input = Input::text("example", "a");
+        assert_eq!(input.hash().unwrap(), format!("{:016x}", fnv1a(b"a\n")));
+
+        let lf = Input::text("lf", "+1\n-2\n").hash().unwrap();
+        for text in &[
+            "+1\r\n-2\r\n",
+            "\u{feff}+1\n-2\n",
+            "\u{feff}+1\r-2 \r\n\r\n",
+            "+1\n-2",
+        ] {
+            assert_eq!(Input::text("copy", *text).hash().unwrap(), lf, "{:?}", text);
+        }
+        assert_ne!(Input::text("other", "+1\n-3\n").hash().unwrap(), lf);
+    }
+
+    #[test]
+    fn normalization() {
+        let skip = |text: &str| normalize("t", text, TrailingBlanks::Skip).unwrap();
+        assert_eq!(skip("\u{feff}+1\r\n-2 \r\n\r\n"), "+1\n-2\n");
+        assert_eq!(skip("a\rb\r\rc\t"), "a\nb\n\nc\n");
+        assert_eq!(skip("\n\n  \n"), "");
+        assert_eq!(skip(" x"), " x\n");
+
+        let reject = |text: &str| normalize("t", text, TrailingBlanks::Reject);
+        assert_eq!(reject("a\r\nb\r\n").unwrap(), "a\nb\n");
+        let err = reject("a\n\nb\n \n\n").unwrap_err();
+        assert_eq!(err.location().line, 4);
+        assert!(err.to_string().contains("no blank lines at the end"));
+
+        let input = Input::text("crlf", "+1\r\n\u{feff}-2\r\n\r\n");
+        assert_eq!(read_lines(&input).unwrap(), vec!["+1", "\u{feff}-2"]);
+        let mut text = String::new();
+        input.open().unwrap().read_to_string(&mut text).unwrap();
+        assert_eq!(text, "+1\n\u{feff}-2\n");
+        assert!(Input::text("bom", "\u{feff}").open().is_ok());
+
+        let err = input.lines(TrailingBlanks::Reject).unwrap_err();
+        assert!(err.to_string().contains("crlf:3"), "{}", err);
+        // the tests share the process, so the policy is left alone
+        assert_eq!(trailing_blanks(), TrailingBlanks::Skip);
+    }
+
+    #[test]
+    fn suspicious_characters() {
+        assert_eq!(suspicious("#1 @ 1,3: 4x4\n\tok\n"), None);
+        assert_eq!(
+            suspicious("1, 6\n1,\u{a0}6\u{200b}\n"),
+            Some((2, 3, '\u{a0}', 2))
+        );
+        assert_eq!(suspicious("ab\x07\n"), Some((1, 3, '\x07', 1)));
+    }
+
+    #[test]
+    fn text_input() {
+        let input = Input::text("example", "+1\n-2\n");
+        assert_eq!(read_lines(&input).unwrap(), vec!["+1", "-2"]);
+        assert_eq!(input.to_string(), "example");
+    }
+}
@@ -0,0 +1,32 @@
+//! Plumbing shared by all the Advent of Code 2018 days: the `Solution` trait
+//! every day implements, error types, helpers for locating, loading, parsing
+//! and iterating over puzzle input, the registry of known answers, profiling,
+//! generating synthetic input, points and rectangles, grids, optionally
+//! parallel loops and pictures.
+pub mod answers;
+#[cfg(feature = "bench")]
+pub mod bench;
+pub mod error;
+pub mod gen;
+pub mod geometry;
+pub mod grid;
+pub mod input;
+pub mod par;
+pub mod parse;
+pub mod profile;
+pub mod render;
+pub mod solution;
+
+pub use answers::{Answers, Check};
+pub use error::{ParseError, Result};
+pub use gen::{Generator, Rng};
+pub use geometry::{Point, Rect};
+pub use grid::Grid;
+pub use input::{
+    list_inputs, parse_lines, read_lines, read_records, resolve_input, set_trailing_blanks, Input,
+    TrailingBlanks,
+};
+pub use parse::FromLine;
+pub use profile::{measure, Stats};
+pub use render::{Image, Render, Rgb};
+pub use solution::{cross_check, AnySolution, Solution, Variant};
@@ -0,0 +1,117 @@
+//! Data parallel loops for the solvers. With the `parallel` feature they run
+//! on all cores, without it they are plain sequential loops, so solvers use
+//! them unconditionally. Either way the results come back in item order, so
+//! the answers don't depend on scheduling.
+use std::cell::Cell;
+
+#[cfg(feature = "parallel")]
+use rayon::prelude::*;
+
+thread_local! {
+    static SEQUENTIAL: Cell<bool> = const { Cell::new(false) };
+}
+
+/// Run `f` with the loops started from this thread kept sequential, to
+/// compare against the parallel results or to measure without interference.
+pub fn sequential<R>(f: impl FnOnce() -> R) -> R {
+    let previous = SEQUENTIAL.with(|s| s.replace(true));
+    let result = f();
+    SEQUENTIAL.with(|s| s.set(previous));
+    result
+}
+
+/// Whether the loops run in parallel here.
+pub fn enabled() -> bool {
+    cfg!(feature = "parallel") && !SEQUENTIAL.with(Cell::get)
+}
+
+/// `f` applied to every item, in order.
+pub fn map<T, R, F>(items: &[T], f: F) -> Vec<R>
+where
+    T: Sync,
+    R: Send,
+    F: Fn(&T) -> R + Sync + Send,
+{
+    #[cfg(feature = "parallel")]
+    {
+        if enabled() {
+            return items.par_iter().map(f).collect();
+        }
+    }
+    items.iter().map(f).collect()
+}
+
+/// The first `Some` that `f` returns for the items, in item order. Items
+/// after it may or may not have been visited.
+pub fn find_map_first<T, R, F>(items: &[T], f: F) -> Option<R>
+where
+    T: Sync,
+    R: Send,
+    F: Fn(&T) -> Option<R> + Sync + Send,
+{
+    #[cfg(feature = "parallel")]
+    {
+        if enabled() {
+            return items.par_iter().find_map_first(f);
+        }
+    }
+    items.iter().find_map(f)
+}
+
+/// Call `f` with the index and contents of every `size` long chunk of
+/// `items`, the last one possibly shorter.
+pub fn for_each_chunk_mut<T, F>(items: &mut [T], size: usize, f: F)
+where
+    T: Send,
+    F: Fn(usize, &mut [T]) + Sync + Send,
+{
+    #[cfg(feature = "parallel")]
+    {
+        if enabled() {
+            items
+                .par_chunks_mut(size)
+                .enumerate()
+                .for_each(|(i, chunk)| f(i, chunk));
+            return;
+        }
+    }
+    items
+        .chunks_mut(size)
+        .enumerate()
+        .for_each(|(i, chunk)| f(i, chunk));
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn keeps_item_order() {
+        let items: Vec<u32> = (0..10_000).collect();
+        let squares = map(&items, |&i| u64::from(i) * u64::from(i));
+        assert_eq!(
+            squares,
+            sequential(|| map(&items, |&i| u64::from(i) * u64::from(i)))
+        );
+        assert!(squares
+            .iter()
+            .enumerate()
+            .all(|(i, &s)| s == (i * i) as u64));
+
+        let first = |&i: &u32| Some(i).filter(|i| i % 997 == 996);
+        assert_eq!(find_map_first(&items, first), Some(996));
+        assert_eq!(sequential(|| find_map_first(&items, first)), Some(996));
+        assert_eq!(find_map_first(&items, |_| None::<u32>), None);
+
+        let mut rows = vec![0; 1001];
+        for_each_chunk_mut(&mut rows, 10, |y, row| row.iter_mut().for_each(|c| *c = y));
+        assert_eq!(rows[..20], [[0; 10], [1; 10]].concat()[..]);
+        assert_eq!(rows[1000], 100);
+    }
+
+    #[test]
+    fn sequential_is_scoped() {
+        assert!(sequential(|| !enabled()));
+        assert_eq!(enabled(), cfg!(feature = "parallel"));
+    }
+}
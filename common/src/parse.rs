@@ -0,0 +1,239 @@
+//! Records read one per line of input, by matching the lines against
+//! patterns like `"#{} @ {},{}: {}x{}"` where every `{}` is a field.
+//!
+//! Matching is lenient about whitespace: whitespace around the line and
+//! around the fields is ignored, and whitespace in the pattern matches any
+//! amount of whitespace in the line, none included, unless it is all there is
+//! between two fields. A field ends where the text after it in the pattern
+//! first matches, the last one at the end of the line.
+use std::fmt::Display;
+use std::ops::Range;
+use std::str::FromStr;
+
+use crate::error::{span, ParseError};
+
+/// A record on one line of input, see [`crate::read_records`].
+pub trait FromLine: Sized {
+    fn from_line(line: &str) -> Result<Self, ParseError>;
+}
+
+macro_rules! from_line_numbers {
+    ($($t:ty),*) => {
+        $(
+            impl FromLine for $t {
+                fn from_line(line: &str) -> Result<$t, ParseError> {
+                    fields("{}", line)?.number()
+                }
+            }
+        )*
+    };
+}
+
+from_line_numbers!(i8, i16, i32, i64, isize, u8, u16, u32, u64, usize);
+
+/// The fields of `line` in the places of the `{}` in `pattern`, or the
+/// column where the line stops matching.
+pub fn fields<'a>(pattern: &str, line: &'a str) -> Result<Fields<'a>, ParseError> {
+    let mut literals = pattern.split("{}").peekable();
+    let first = literals.next().unwrap_or("");
+    let start = line.len() - line.trim_start().len();
+    let mut pos = match match_literal(first, &line[start..]) {
+        Some(len) => start + len,
+        None => return Err(expected(first, line, start)),
+    };
+    let mut spans = Vec::new();
+    while let Some(literal) = literals.next() {
+        let rest = &line[pos..];
+        let (end, next) = if literal.is_empty() && literals.peek().is_none() {
+            (rest.len(), rest.len())
+        } else {
+            find_literal(literal, rest).ok_or_else(|| expected(literal, line, pos))?
+        };
+        spans.push(span(line, rest[..end].trim()));
+        pos += next;
+    }
+    let rest = line[pos..].trim();
+    if !rest.is_empty() {
+        return Err(ParseError::syntax(span(line, rest), "the end of the line"));
+    }
+    Ok(Fields {
+        line,
+        spans: spans.into_iter(),
+    })
+}
+
+/// The error for a line without `literal` from column `pos` on.
+fn expected(literal: &str, line: &str, pos: usize) -> ParseError {
+    let rest = line[pos..].trim();
+    let columns = if rest.is_empty() {
+        line.len()..line.len()
+    } else {
+        span(line, rest)
+    };
+    match literal.trim() {
+        "" => ParseError::syntax(columns, "whitespace"),
+        literal => ParseError::syntax(columns, format!("'{}'", literal)),
+    }
+}
+
+/// Length of the start of `text` that matches `literal`, if it does.
+fn match_literal(literal: &str, text: &str) -> Option<usize> {
+    let (mut literal, mut rest) = (literal, text);
+    while let Some(c) = literal.chars().next() {
+        if c.is_whitespace() {
+            literal = literal.trim_start();
+            rest = rest.trim_start();
+        } else {
+            rest = rest.strip_prefix(c)?;
+            literal = &literal[c.len_utf8()..];
+        }
+    }
+    Some(text.len() - rest.len())
+}
+
+/// Where `literal` first matches in `text`, as the offsets of its start and
+/// its end.
+fn find_literal(literal: &str, text: &str) -> Option<(usize, usize)> {
+    // whitespace alone has to match something to separate two fields
+    let min_len = (literal.trim().is_empty() && !literal.is_empty()) as usize;
+    text.char_indices()
+        .map(|(i, _)| i)
+        .chain(Some(text.len()))
+        .find_map(|i| {
+            let len = match_literal(literal, &text[i..]).filter(|&len| len >= min_len)?;
+            Some((i, i + len))
+        })
+}
+
+/// The fields of a matched line, taken in order.
+#[derive(Debug)]
+pub struct Fields<'a> {
+    line: &'a str,
+    spans: std::vec::IntoIter<Range<usize>>,
+}
+
+impl<'a> Fields<'a> {
+    fn next_span(&mut self) -> Range<usize> {
+        self.spans
+            .next()
+            .expect("more fields taken than there are in the pattern")
+    }
+
+    /// The next field as it is, without surrounding whitespace.
+    pub fn str(&mut self) -> &'a str {
+        let columns = self.next_span();
+        &self.line[columns]
+    }
+
+    /// The next field as a number, or anything else parsed with `FromStr`.
+    pub fn number<T>(&mut self) -> Result<T, ParseError>
+    where
+        T: FromStr,
+        T::Err: Display,
+    {
+        let columns = self.next_span();
+        self.line[columns.clone()]
+            .parse()
+            .map_err(|e| ParseError::number(columns, e))
+    }
+
+    /// The next field converted by `convert`, its error reported as an
+    /// invalid value.
+    pub fn with<T, E, F>(&mut self, convert: F) -> Result<T, ParseError>
+    where
+        E: Display,
+        F: FnOnce(&'a str) -> Result<T, E>,
+    {
+        let columns = self.next_span();
+        convert(&self.line[columns.clone()]).map_err(|e| ParseError::value(columns, e.to_string()))
+    }
+
+    /// The next field as a record of its own.
+    pub fn record<T: FromLine>(&mut self) -> Result<T, ParseError> {
+        let columns = self.next_span();
+        T::from_line(&self.line[columns.clone()]).map_err(|e| e.offset(columns.start))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn strs(pattern: &str, line: &str) -> Result<Vec<String>, ParseError> {
+        let mut fields = fields(pattern, line)?;
+        let n = pattern.matches("{}").count();
+        Ok((0..n).map(|_| fields.str().to_string()).collect())
+    }
+
+    fn columns(err: ParseError) -> Range<usize> {
+        err.location().columns.clone()
+    }
+
+    #[test]
+    fn matching() {
+        let claim = "#{} @ {},{}: {}x{}";
+        assert_eq!(
+            strs(claim, "#12 @ 3,2: 5x4").unwrap(),
+            ["12", "3", "2", "5", "4"]
+        );
+        assert_eq!(
+            strs("[{}] {}", "[1518-11-01 00:00] wakes up").unwrap()[1],
+            "wakes up"
+        );
+        assert_eq!(strs("{} {}", "falls asleep").unwrap(), ["falls", "asleep"]);
+        assert_eq!(strs("wakes up", "wakes up").unwrap(), Vec::<String>::new());
+        assert_eq!(strs("{}", "").unwrap(), [""]);
+    }
+
+    #[test]
+    fn whitespace() {
+        let claim = "#{} @ {},{}: {}x{}";
+        assert_eq!(
+            strs(claim, "  #12@3 , 2 :5 x 4\t").unwrap(),
+            ["12", "3", "2", "5", "4"]
+        );
+        assert_eq!(strs("{}, {}", "1,6").unwrap(), ["1", "6"]);
+        assert_eq!(strs("{} {}", "a    b").unwrap(), ["a", "b"]);
+        assert_eq!(strs("wakes up", " wakes   up ").unwrap().len(), 0);
+        assert!(strs("{} {}", "ab").is_err());
+        assert!(strs("wakes up", "wakesup").is_ok());
+    }
+
+    #[test]
+    fn errors() {
+        let claim = "#{} @ {},{}: {}x{}";
+        let err = fields(claim, "#1 @ 3,2 5x4").unwrap_err();
+        assert_eq!(err.to_string(), "parse error: expected ':'\n  --> column 8");
+        assert_eq!(columns(err), 7..12);
+        assert_eq!(columns(fields(claim, "1 @ 3,2: 5x4").unwrap_err()), 0..12);
+        assert_eq!(columns(fields(claim, "#1 @ 3,2: 5").unwrap_err()), 10..11);
+        let trailing = fields("{}, {}", "1, 6]").map(|mut f| (f.str(), f.str()));
+        assert_eq!(trailing.unwrap(), ("1", "6]"));
+        let trailing = fields("[{}]", "[1] x").unwrap_err();
+        assert_eq!(columns(trailing.clone()), 4..5);
+        assert!(trailing
+            .to_string()
+            .contains("expected the end of the line"));
+
+        let mut f = fields("{}x{}", " 5 x q ").unwrap();
+        assert_eq!(f.number::<u32>(), Ok(5));
+        assert_eq!(columns(f.number::<u32>().unwrap_err()), 5..6);
+        let mut f = fields("{}: {}", "id: 300").unwrap();
+        assert_eq!(f.str(), "id");
+        let err = f.with(|s| s.parse::<u8>()).unwrap_err();
+        assert!(matches!(err, ParseError::Value { .. }));
+        assert_eq!(columns(err), 4..7);
+    }
+
+    #[test]
+    fn numbers_and_records() {
+        assert_eq!(i32::from_line(" +7 "), Ok(7));
+        assert_eq!(i64::from_line("-12"), Ok(-12));
+        assert_eq!(columns(u8::from_line(" 256").unwrap_err()), 1..4);
+        let mut f = fields("{} = {}", "x = 4q").unwrap();
+        assert_eq!(f.str(), "x");
+        let err = f.record::<u32>().unwrap_err();
+        assert!(matches!(err, ParseError::Number { .. }));
+        assert_eq!(columns(err), 4..6);
+    }
+}
@@ -0,0 +1,78 @@
+use std::alloc::{GlobalAlloc, Layout, System};
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::time::{Duration, Instant};
+
+static ALLOCATED: AtomicUsize = AtomicUsize::new(0);
+static PEAK: AtomicUsize = AtomicUsize::new(0);
+
+/// Global allocator that forwards to the system allocator while keeping
+/// track of the bytes currently allocated and the high-water mark.
+///
+/// Binaries opt in with
+/// `#[global_allocator] static GLOBAL: CountingAllocator = CountingAllocator;`,
+/// without it all heap figures are reported as zero.
+pub struct CountingAllocator;
+
+fn add(size: usize) {
+    let now = ALLOCATED.fetch_add(size, Ordering::Relaxed) + size;
+    PEAK.fetch_max(now, Ordering::Relaxed);
+}
+
+fn sub(size: usize) {
+    ALLOCATED.fetch_sub(size, Ordering::Relaxed);
+}
+
+unsafe impl GlobalAlloc for CountingAllocator {
+    unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+        let ptr = System.alloc(layout);
+        if !ptr.is_null() {
+            add(layout.size());
+        }
+        ptr
+    }
+
+    unsafe fn alloc_zeroed(&self, layout: Layout) -> *mut u8 {
+        let ptr = System.alloc_zeroed(layout);
+        if !ptr.is_null() {
+            add(layout.size());
+        }
+        ptr
+    }
+
+    unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+        System.dealloc(ptr, layout);
+        sub(layout.size());
+    }
+
+    unsafe fn realloc(&self, ptr: *mut u8, layout: Layout, new_size: usize) -> *mut u8 {
+        let new_ptr = System.realloc(ptr, layout, new_size);
+        if !new_ptr.is_null() {
+            sub(layout.size());
+            add(new_size);
+        }
+        new_ptr
+    }
+}
+
+/// Time and heap usage of one stage of a solution.
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub struct Stats {
+    pub elapsed: Duration,
+    /// Most bytes allocated at any point during the stage, on top of what
+    /// was already allocated when it started.
+    pub peak_heap: usize,
+}
+
+/// Run `f` and measure how long it takes and how much heap it needs.
+///
+/// The heap counters are process wide, so figures are only accurate when
+/// nothing else allocates concurrently.
+pub fn measure<T>(f: impl FnOnce() -> T) -> (T, Stats) {
+    let base = ALLOCATED.load(Ordering::Relaxed);
+    PEAK.store(base, Ordering::Relaxed);
+    let start = Instant::now();
+    let value = f();
+    let elapsed = start.elapsed();
+    let peak_heap = PEAK.load(Ordering::Relaxed).saturating_sub(base);
+    (value, Stats { elapsed, peak_heap })
+}
@@ -0,0 +1,529 @@
+//! Pictures of what the solvers work on, for debugging: a grid of coloured
+//! cells with a title and a legend, written as a PPM or PNG raster or as SVG.
+use std::fs::File;
+use std::io::{self, BufWriter, Write};
+use std::path::Path;
+
+use crate::error::Result;
+use crate::solution::Solution;
+
+/// A [`Solution`] that can draw what one of its parts is about.
+pub trait Render: Solution {
+    /// Picture of `part` solved on `parsed`.
+    fn render(parsed: &Self::Parsed, part: u32) -> Result<Image>;
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Rgb(pub u8, pub u8, pub u8);
+
+impl Rgb {
+    pub const WHITE: Rgb = Rgb(255, 255, 255);
+    pub const BLACK: Rgb = Rgb(0, 0, 0);
+    pub const GREY: Rgb = Rgb(225, 225, 225);
+
+    /// The colour `t` of the way from `self` to `other`.
+    pub fn mix(self, other: Rgb, t: f64) -> Rgb {
+        let channel =
+            |a: u8, b: u8| (f64::from(a) + (f64::from(b) - f64::from(a)) * t).round() as u8;
+        Rgb(
+            channel(self.0, other.0),
+            channel(self.1, other.1),
+            channel(self.2, other.2),
+        )
+    }
+
+    fn hex(self) -> String {
+        format!("#{:02x}{:02x}{:02x}", self.0, self.1, self.2)
+    }
+}
+
+/// Colour of `t`, clamped to `0..=1`, on a white, yellow, red, dark red scale,
+/// so that zero blends in with the background.
+pub fn heat(t: f64) -> Rgb {
+    const STOPS: [Rgb; 4] = [
+        Rgb(255, 255, 255),
+        Rgb(250, 210, 50),
+        Rgb(200, 30, 30),
+        Rgb(60, 0, 0),
+    ];
+    let t = t.clamp(0.0, 1.0) * (STOPS.len() - 1) as f64;
+    let i = (t as usize).min(STOPS.len() - 2);
+    STOPS[i].mix(STOPS[i + 1], t - i as f64)
+}
+
+/// Colour number `i` of an endless palette for telling things apart, where
+/// nearby numbers get very different hues.
+pub fn category(i: usize) -> Rgb {
+    // golden ratio steps around the hue circle never line up
+    let hue = (i as f64 * 0.618_033_988_749_895).fract();
+    let lightness = [0.5, 0.35, 0.65][i % 3];
+    hsl(hue, 0.7, lightness)
+}
+
+fn hsl(hue: f64, saturation: f64, lightness: f64) -> Rgb {
+    let c = (1.0 - (2.0 * lightness - 1.0).abs()) * saturation;
+    let h = hue * 6.0;
+    let x = c * (1.0 - (h % 2.0 - 1.0).abs());
+    let (r, g, b) = match h as u32 {
+        0 => (c, x, 0.0),
+        1 => (x, c, 0.0),
+        2 => (0.0, c, x),
+        3 => (0.0, x, c),
+        4 => (x, 0.0, c),
+        _ => (c, 0.0, x),
+    };
+    let m = lightness - c / 2.0;
+    let channel = |v: f64| ((v + m) * 255.0).round() as u8;
+    Rgb(channel(r), channel(g), channel(b))
+}
+
+/// A grid of coloured cells, drawn `scale` pixels to the side, under a title
+/// and above a legend of colours and what they mean.
+#[derive(Debug, Clone)]
+pub struct Image {
+    width: usize,
+    height: usize,
+    background: Rgb,
+    cells: Vec<Rgb>,
+    pub scale: usize,
+    pub title: String,
+    pub legend: Vec<(Rgb, String)>,
+}
+
+impl Image {
+    pub fn new(width: usize, height: usize, background: Rgb) -> Image {
+        Image {
+            width,
+            height,
+            background,
+            cells: vec![background; width * height],
+            scale: 1,
+            title: String::new(),
+            legend: Vec::new(),
+        }
+    }
+
+    pub fn width(&self) -> usize {
+        self.width
+    }
+
+    pub fn height(&self) -> usize {
+        self.height
+    }
+
+    pub fn get(&self, x: usize, y: usize) -> Rgb {
+        self.cells[y * self.width + x]
+    }
+
+    pub fn set(&mut self, x: usize, y: usize, color: Rgb) {
+        self.cells[y * self.width + x] = color;
+    }
+
+    /// Colour the `w` by `h` cells from `x`, `y`, as far as they are inside.
+    pub fn fill(&mut self, x: usize, y: usize, w: usize, h: usize, color: Rgb) {
+        for y in y..(y + h).min(self.height) {
+            for x in x..(x + w).min(self.width) {
+                self.set(x, y, color);
+            }
+        }
+    }
+
+    pub fn add_legend(&mut self, color: Rgb, label: impl Into<String>) {
+        self.legend.push((color, label.into()));
+    }
+
+    /// Write to `path`, as PPM, PNG or SVG depending on its extension.
+    pub fn save(&self, path: &Path) -> Result<()> {
+        let extension = path.extension().and_then(|e| e.to_str());
+        let extension = extension.map(str::to_ascii_lowercase).unwrap_or_default();
+        if !["ppm", "png", "svg"].contains(&extension.as_str()) {
+            let err = format!(
+                "Unable to tell the image format of '{}', use .ppm, .png or .svg",
+                path.display()
+            );
+            return Err(err.into());
+        }
+        let file = File::create(path)
+            .map_err(|e| format!("Unable to create '{}': {}", path.display(), e))?;
+        let mut out = BufWriter::new(file);
+        match extension.as_str() {
+            "ppm" => self.write_ppm(&mut out)?,
+            "png" => self.write_png(&mut out)?,
+            _ => self.write_svg(&mut out)?,
+        }
+        out.flush()?;
+        Ok(())
+    }
+
+    /// Binary PPM (P6), the simplest raster format there is.
+    pub fn write_ppm(&self, out: &mut impl Write) -> io::Result<()> {
+        let raster = self.rasterize();
+        write!(out, "P6\n{} {}\n255\n", raster.width, raster.height)?;
+        out.write_all(&raster.bytes())
+    }
+
+    pub fn write_png(&self, out: impl Write) -> Result<()> {
+        let raster = self.rasterize();
+        let mut encoder = png::Encoder::new(out, raster.width as u32, raster.height as u32);
+        encoder.set_color(png::ColorType::Rgb);
+        encoder.set_depth(png::BitDepth::Eight);
+        let mut writer = encoder.write_header()?;
+        writer.write_image_data(&raster.bytes())?;
+        Ok(())
+    }
+
+    /// SVG with one rectangle per run of same coloured cells in a row.
+    pub fn write_svg(&self, out: &mut impl Write) -> io::Result<()> {
+        let layout = self.layout();
+        writeln!(
+            out,
+            r#"<svg xmlns="http://www.w3.org/2000/svg" width="{w}" height="{h}" viewBox="0 0 {w} {h}" shape-rendering="crispEdges" font-family="monospace" font-size="{size}">"#,
+            w = layout.width,
+            h = layout.height,
+            size = LINE_HEIGHT - 2 * TEXT_SCALE,
+        )?;
+        writeln!(out, r#"<rect width="100%" height="100%" fill="white"/>"#)?;
+        if let Some((x, y)) = layout.title {
+            let baseline = y + FONT_HEIGHT * TEXT_SCALE;
+            writeln!(
+                out,
+                r#"<text x="{}" y="{}">{}</text>"#,
+                x,
+                baseline,
+                escape(&self.title)
+            )?;
+        }
+        let (x0, y0) = layout.picture;
+        writeln!(
+            out,
+            r#"<g transform="translate({} {}) scale({})">"#,
+            x0, y0, self.scale
+        )?;
+        let (w, h) = (self.width, self.height);
+        let background = self.background.hex();
+        writeln!(
+            out,
+            r#"<rect width="{}" height="{}" fill="{}"/>"#,
+            w, h, background
+        )?;
+        for y in 0..h {
+            let row = &self.cells[y * w..(y + 1) * w];
+            let mut x = 0;
+            while x < w {
+                let run = row[x..].iter().take_while(|&&c| c == row[x]).count();
+                if row[x] != self.background {
+                    writeln!(
+                        out,
+                        r#"<rect x="{}" y="{}" width="{}" height="1" fill="{}"/>"#,
+                        x,
+                        y,
+                        run,
+                        row[x].hex()
+                    )?;
+                }
+                x += run;
+            }
+        }
+        writeln!(out, "</g>")?;
+        for ((color, label), &(x, y)) in self.legend.iter().zip(&layout.legend) {
+            let side = FONT_HEIGHT * TEXT_SCALE;
+            writeln!(
+                out,
+                r#"<rect x="{}" y="{}" width="{}" height="{}" fill="{}" stroke="black"/>"#,
+                x,
+                y,
+                side,
+                side,
+                color.hex()
+            )?;
+            writeln!(
+                out,
+                r#"<text x="{}" y="{}">{}</text>"#,
+                x + side + 2 * TEXT_SCALE,
+                y + side,
+                escape(label)
+            )?;
+        }
+        writeln!(out, "</svg>")
+    }
+
+    /// Where the title, picture and legend entries go, in pixels.
+    fn layout(&self) -> Layout {
+        let text_width = |text: &str| text.chars().count() * CHAR_WIDTH;
+        let mut width = self.width * self.scale;
+        let mut y = MARGIN;
+        let title = if self.title.is_empty() {
+            None
+        } else {
+            width = width.max(text_width(&self.title));
+            y += LINE_HEIGHT;
+            Some((MARGIN, MARGIN))
+        };
+        let picture = (MARGIN, y);
+        y += self.height * self.scale + MARGIN;
+        let mut legend = Vec::new();
+        for (_, label) in &self.legend {
+            let swatch = (FONT_HEIGHT + 2) * TEXT_SCALE;
+            width = width.max(swatch + text_width(label));
+            legend.push((MARGIN, y));
+            y += LINE_HEIGHT;
+        }
+        Layout {
+            width: width + 2 * MARGIN,
+            height: y,
+            title,
+            picture,
+            legend,
+        }
+    }
+
+    fn rasterize(&self) -> Raster {
+        let layout = self.layout();
+        let mut raster = Raster::new(layout.width, layout.height);
+        if let Some((x, y)) = layout.title {
+            raster.text(x, y, &self.title);
+        }
+        let (x0, y0) = layout.picture;
+        for y in 0..self.height {
+            for x in 0..self.width {
+                let (px, py) = (x0 + x * self.scale, y0 + y * self.scale);
+                raster.fill(px, py, self.scale, self.scale, self.get(x, y));
+            }
+        }
+        for ((color, label), &(x, y)) in self.legend.iter().zip(&layout.legend) {
+            let side = FONT_HEIGHT * TEXT_SCALE;
+            raster.fill(x, y, side, side, Rgb::BLACK);
+            raster.fill(x + 1, y + 1, side - 2, side - 2, *color);
+            raster.text(x + side + 2 * TEXT_SCALE, y, label);
+        }
+        raster
+    }
+}
+
+const MARGIN: usize = 4;
+/// Pixels per font pixel in rasters.
+const TEXT_SCALE: usize = 2;
+const FONT_HEIGHT: usize = 5;
+const CHAR_WIDTH: usize = 4 * TEXT_SCALE;
+const LINE_HEIGHT: usize = (FONT_HEIGHT + 2) * TEXT_SCALE;
+
+struct Layout {
+    width: usize,
+    height: usize,
+    title: Option<(usize, usize)>,
+    picture: (usize, usize),
+    legend: Vec<(usize, usize)>,
+}
+
+struct Raster {
+    width: usize,
+    height: usize,
+    pixels: Vec<Rgb>,
+}
+
+impl Raster {
+    fn new(width: usize, height: usize) -> Raster {
+        Raster {
+            width,
+            height,
+            pixels: vec![Rgb::WHITE; width * height],
+        }
+    }
+
+    fn fill(&mut self, x: usize, y: usize, w: usize, h: usize, color: Rgb) {
+        for y in y..(y + h).min(self.height) {
+            let row = y * self.width;
+            for pixel in &mut self.pixels[row + x.min(self.width)..row + (x + w).min(self.width)] {
+                *pixel = color;
+            }
+        }
+    }
+
+    /// `text` in black with its top left corner at `x`, `y`.
+    fn text(&mut self, x: usize, y: usize, text: &str) {
+        for (i, ch) in text.chars().enumerate() {
+            let glyph = glyph(ch);
+            for (row, bits) in glyph.chunks(3).enumerate() {
+                for (col, &bit) in bits.iter().enumerate() {
+                    if bit == b'#' {
+                        let (px, py) =
+                            (x + i * CHAR_WIDTH + col * TEXT_SCALE, y + row * TEXT_SCALE);
+                        self.fill(px, py, TEXT_SCALE, TEXT_SCALE, Rgb::BLACK);
+                    }
+                }
+            }
+        }
+    }
+
+    fn bytes(&self) -> Vec<u8> {
+        self.pixels
+            .iter()
+            .flat_map(|p| vec![p.0, p.1, p.2])
+            .collect()
+    }
+}
+
+fn escape(text: &str) -> String {
+    text.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+}
+
+/// 3x5 pixel font, row by row. Lowercase letters are drawn as capitals and
+/// anything missing as a question mark.
+const FONT: &[(char, &str)] = &[
+    (' ', "..............."),
+    ('0', "####.##.##.####"),
+    ('1', ".#.##..#..#.###"),
+    ('2', "###..#####..###"),
+    ('3', "###..####..####"),
+    ('4', "#.##.####..#..#"),
+    ('5', "####..###..####"),
+    ('6', "####..####.####"),
+    ('7', "###..#..#..#..#"),
+    ('8', "####.#####.####"),
+    ('9', "####.####..####"),
+    ('A', ".#.#.#####.##.#"),
+    ('B', "##.#.###.#.###."),
+    ('C', ".###..#..#...##"),
+    ('D', "##.#.##.##.###."),
+    ('E', "####..##.#..###"),
+    ('F', "####..##.#..#.."),
+    ('G', ".###..#.##.#.##"),
+    ('H', "#.##.#####.##.#"),
+    ('I', "###.#..#..#.###"),
+    ('J', "..#..#..##.#.#."),
+    ('K', "#.##.###.#.##.#"),
+    ('L', "#..#..#..#..###"),
+    ('M', "#.########.##.#"),
+    ('N', "##.#.##.##.##.#"),
+    ('O', ".#.#.##.##.#.#."),
+    ('P', "##.#.###.#..#.."),
+    ('Q', ".#.#.##.###..##"),
+    ('R', "##.#.###.#.##.#"),
+    ('S', ".###...#...###."),
+    ('T', "###.#..#..#..#."),
+    ('U', "#.##.##.##.####"),
+    ('V', "#.##.##.##.#.#."),
+    ('W', "#.##.########.#"),
+    ('X', "#.##.#.#.#.##.#"),
+    ('Y', "#.##.#.#..#..#."),
+    ('Z', "###..#.#.#..###"),
+    ('#', "#.#####.#####.#"),
+    ('-', "......###......"),
+    ('+', "....#.###.#...."),
+    ('.', ".............#."),
+    (',', "..........#.#.."),
+    (':', "....#.....#...."),
+    ('=', "...###...###..."),
+    ('<', "..#.#.#...#...#"),
+    ('>', "#...#...#.#.#.."),
+    ('(', ".#.#..#..#...#."),
+    (')', ".#...#..#..#.#."),
+    ('/', "..#..#.#.#..#.."),
+    ('?', "###..#.#.....#."),
+];
+
+fn glyph(ch: char) -> &'static [u8] {
+    let ch = ch.to_ascii_uppercase();
+    let found = FONT.iter().find(|&&(c, _)| c == ch);
+    found
+        .map_or(FONT[FONT.len() - 1].1, |(_, rows)| rows)
+        .as_bytes()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn example() -> Image {
+        let mut image = Image::new(3, 2, Rgb::WHITE);
+        image.fill(1, 0, 5, 1, Rgb(10, 20, 30));
+        image.set(0, 1, Rgb::BLACK);
+        image.scale = 2;
+        image
+    }
+
+    #[test]
+    fn font_glyphs_are_complete() {
+        for (ch, rows) in FONT {
+            assert_eq!(rows.len(), 15, "{:?}", ch);
+        }
+        assert_eq!(glyph('a'), glyph('A'));
+        assert_eq!(glyph('~'), glyph('?'));
+    }
+
+    #[test]
+    fn palettes() {
+        assert_eq!(heat(0.0), Rgb::WHITE);
+        assert_eq!(heat(-1.0), Rgb::WHITE);
+        assert_eq!(heat(1.0), heat(2.0));
+        assert_eq!(Rgb::BLACK.mix(Rgb::WHITE, 0.5), Rgb(128, 128, 128));
+        let colors: Vec<Rgb> = (0..30).map(category).collect();
+        for (i, a) in colors.iter().enumerate() {
+            assert!(!colors[i + 1..].contains(a), "{:?} repeats", a);
+        }
+    }
+
+    #[test]
+    fn ppm() {
+        let mut ppm = Vec::new();
+        example().write_ppm(&mut ppm).unwrap();
+        let (w, h) = (6 + 2 * MARGIN, 4 + 2 * MARGIN);
+        let header = format!("P6\n{} {}\n255\n", w, h);
+        assert!(ppm.starts_with(header.as_bytes()));
+        let pixels = &ppm[header.len()..];
+        assert_eq!(pixels.len(), w * h * 3);
+        let pixel = |x: usize, y: usize| {
+            let i = ((y + MARGIN) * w + x + MARGIN) * 3;
+            Rgb(pixels[i], pixels[i + 1], pixels[i + 2])
+        };
+        assert_eq!(pixel(0, 0), Rgb::WHITE);
+        assert_eq!(pixel(2, 0), Rgb(10, 20, 30));
+        assert_eq!(pixel(5, 1), Rgb(10, 20, 30));
+        assert_eq!(pixel(1, 3), Rgb::BLACK);
+    }
+
+    #[test]
+    fn png() {
+        let mut png = Vec::new();
+        example().write_png(&mut png).unwrap();
+        assert!(png.starts_with(b"\x89PNG\r\n\x1a\n"));
+    }
+
+    #[test]
+    fn svg() {
+        let mut image = example();
+        image.title = "Claims <3".to_string();
+        image.add_legend(Rgb::BLACK, "overlap & more");
+        let mut svg = Vec::new();
+        image.write_svg(&mut svg).unwrap();
+        let svg = String::from_utf8(svg).unwrap();
+        assert!(svg.contains(r##"<rect x="1" y="0" width="2" height="1" fill="#0a141e"/>"##));
+        assert!(svg.contains(r##"<rect x="0" y="1" width="1" height="1" fill="#000000"/>"##));
+        assert!(svg.contains(">Claims &lt;3</text>"));
+        assert!(svg.contains(">overlap &amp; more</text>"));
+        assert!(svg.trim_end().ends_with("</svg>"));
+    }
+
+    #[test]
+    fn legend_makes_room() {
+        let mut image = example();
+        let plain = image.layout();
+        image.title = "A long title".to_string();
+        image.add_legend(Rgb::BLACK, "one");
+        image.add_legend(Rgb::WHITE, "two");
+        let layout = image.layout();
+        assert_eq!(layout.width, 12 * CHAR_WIDTH + 2 * MARGIN);
+        assert_eq!(layout.height, plain.height + 3 * LINE_HEIGHT);
+        assert_eq!(layout.picture, (MARGIN, MARGIN + LINE_HEIGHT));
+    }
+
+    #[test]
+    fn unknown_format() {
+        let path = std::env::temp_dir().join("aoc2018-render.gif");
+        let err = example().save(&path).unwrap_err();
+        assert!(err.to_string().contains(".ppm, .png or .svg"), "{}", err);
+        assert!(!path.exists());
+    }
+}
@@ -0,0 +1,119 @@
+use std::any::Any;
+use std::fmt::Display;
+
+use crate::error::Result;
+use crate::input::Input;
+
+/// A day's puzzle, split into parsing and the two parts.
+pub trait Solution {
+    const DAY: u32;
+    const TITLE: &'static str;
+    /// Path of the input bundled with the day.
+    const DEFAULT_INPUT: &'static str;
+
+    /// The parsed puzzle input both parts work on.
+    type Parsed: 'static;
+    type Answer1: Display;
+    type Answer2: Display;
+
+    fn parse(input: &Input) -> Result<Self::Parsed>;
+    fn part1(parsed: &Self::Parsed) -> Result<Self::Answer1>;
+    fn part2(parsed: &Self::Parsed) -> Result<Self::Answer2>;
+
+    /// Alternative implementations of the parts, kept around for comparison.
+    const VARIANTS: &'static [Variant<Self::Parsed>] = &[];
+}
+
+/// A named alternative implementation of one part of a [`Solution`].
+///
+/// `solve` gets the raw input as well, for variants that do their own
+/// parsing.
+pub struct Variant<P: 'static> {
+    pub name: &'static str,
+    pub part: u32,
+    pub solve: fn(&Input, &P) -> Result<String>,
+}
+
+/// Check that every variant of `S` agrees with its part on `input`.
+pub fn cross_check<S: Solution>(input: &Input) -> Result<()> {
+    let parsed = S::parse(input)?;
+    for variant in S::VARIANTS {
+        let expected = match variant.part {
+            1 => S::part1(&parsed)?.to_string(),
+            _ => S::part2(&parsed)?.to_string(),
+        };
+        let answer = (variant.solve)(input, &parsed)?;
+        if answer != expected {
+            return Err(format!(
+                "Variant '{}' of day {} part {} answered {} instead of {} on {}",
+                variant.name,
+                S::DAY,
+                variant.part,
+                answer,
+                expected,
+                input
+            )
+            .into());
+        }
+    }
+    Ok(())
+}
+
+/// Object safe view of a [`Solution`], so that days with different input and
+/// answer types can be kept in one table and run generically.
+pub trait AnySolution: Sync {
+    fn day(&self) -> u32;
+    fn title(&self) -> &'static str;
+    fn default_input(&self) -> &'static str;
+    fn parse(&self, input: &Input) -> Result<Box<dyn Any>>;
+    /// Solve `part` (1 or 2) of input returned by [`AnySolution::parse`].
+    fn solve(&self, parsed: &dyn Any, part: u32) -> Result<String>;
+    /// Names and parts of the registered [`Variant`]s.
+    fn variants(&self) -> Vec<(&'static str, u32)>;
+    /// Solve with the variant called `name` instead of the default part.
+    fn solve_variant(&self, input: &Input, parsed: &dyn Any, name: &str) -> Result<String>;
+}
+
+impl<S: Solution + Sync> AnySolution for S {
+    fn day(&self) -> u32 {
+        S::DAY
+    }
+
+    fn title(&self) -> &'static str {
+        S::TITLE
+    }
+
+    fn default_input(&self) -> &'static str {
+        S::DEFAULT_INPUT
+    }
+
+    fn parse(&self, input: &Input) -> Result<Box<dyn Any>> {
+        Ok(Box::new(S::parse(input)?))
+    }
+
+    fn solve(&self, parsed: &dyn Any, part: u32) -> Result<String> {
+        let parsed = downcast::<S>(parsed)?;
+        match part {
+            1 => Ok(S::part1(parsed)?.to_string()),
+            2 => Ok(S::part2(parsed)?.to_string()),
+            _ => Err(format!("Day {} has no part {}", S::DAY, part).into()),
+        }
+    }
+    fn variants(&self) -> Vec<(&'static str, u32)> {
+        S::VARIANTS.iter().map(|v| (v.name, v.part)).collect()
+    }
+
+    fn solve_variant(&self, input: &Input, parsed: &dyn Any, name: &str) -> Result<String> {
+        let variant = S::VARIANTS
+            .iter()
+            .find(|v| v.name == name)
+            .ok_or_else(|| format!("Day {} has no variant '{}'", S::DAY, name))?;
+        (variant.solve)(input, downcast::<S>(parsed)?)
+    }
+}
+
+fn downcast<S: Solution>(parsed: &dyn Any) -> Result<&S::Parsed> {
+    Ok(parsed
+        .downcast_ref::<S::Parsed>()
+        .ok_or_else(|| format!("Input for day {} has the wrong type", S::DAY))?)
+}
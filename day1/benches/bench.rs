@@ -0,0 +1,49 @@
+#[macro_use]
+extern crate bencher;
+
+use aoc_common::{bench, Input};
+use bencher::{Bencher, TestDescAndFn};
+use day1::Day1;
+
+fn real() -> Input {
+    bench::load(day1::DEFAULT_INPUT)
+}
+
+// Generated input with 8000 frequency changes.
+fn scaled() -> Input {
+    aoc_common::gen::generate::<Day1>(1, 8000, &Default::default())
+}
+
+fn parse(b: &mut Bencher) {
+    bench::parse::<Day1>(b, &real())
+}
+fn part1(b: &mut Bencher) {
+    bench::part1::<Day1>(b, &real())
+}
+fn part2(b: &mut Bencher) {
+    bench::part2::<Day1>(b, &real())
+}
+fn parse_scaled(b: &mut Bencher) {
+    bench::parse::<Day1>(b, &scaled())
+}
+fn part1_scaled(b: &mut Bencher) {
+    bench::part1::<Day1>(b, &scaled())
+}
+fn part2_scaled(b: &mut Bencher) {
+    bench::part2::<Day1>(b, &scaled())
+}
+
+fn variants() -> Vec<TestDescAndFn> {
+    bench::variants::<Day1>(&real())
+}
+
+benchmark_group!(
+    benches,
+    parse,
+    part1,
+    part2,
+    parse_scaled,
+    part1_scaled,
+    part2_scaled
+);
+benchmark_main!(benches, variants);
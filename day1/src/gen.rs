@@ -0,0 +1,87 @@
+//! Frequency change lists whose first repeated frequency is reached during a
+//! chosen pass over the list.
+use std::collections::HashSet;
+
+use aoc_common::{Generator, Result, Rng};
+
+use crate::Day1;
+
+pub struct Params {
+    /// Pass over the list, counting from 0, during which the first frequency
+    /// is repeated.
+    pub repeat_pass: u64,
+    /// How far apart, in multiples of the total drift per pass, the
+    /// frequencies of one pass are spread.
+    pub spread: i64,
+}
+
+impl Default for Params {
+    fn default() -> Params {
+        Params {
+            repeat_pass: 10,
+            spread: 3,
+        }
+    }
+}
+
+impl Generator for Day1 {
+    type Params = Params;
+
+    fn generate(rng: &mut Rng, size: usize, params: &Params) -> String {
+        // Frequency q * n + k is freqs[k] + q * drift. Every frequency in a
+        // pass gets its own remainder modulo the drift, except for one
+        // planted pair that is exactly `repeat_pass` drifts apart.
+        let n = size.max(2);
+        let drift = 2 * n as i64;
+        let mut remainders: Vec<i64> = (0..drift).collect();
+        rng.shuffle(&mut remainders);
+        let mut freqs: Vec<i64> = remainders[..n]
+            .iter()
+            .map(|r| r + drift * rng.range(0, params.spread))
+            .collect();
+
+        // `repeat_pass` passes later, change `from` reaches the frequency of
+        // change `to` in the first pass. Within one pass, `to` comes first.
+        let mut from = rng.below(n as u64) as usize;
+        let mut to = rng.below(n as u64 - 1) as usize;
+        if to >= from {
+            to += 1;
+        }
+        if params.repeat_pass == 0 && to > from {
+            std::mem::swap(&mut to, &mut from);
+        }
+        freqs[to] = freqs[from] + drift * params.repeat_pass as i64;
+
+        // the first frequency is always 0
+        let start = freqs[0];
+        freqs.iter_mut().for_each(|f| *f -= start);
+        freqs.push(drift);
+
+        let mut text = String::new();
+        for pair in freqs.windows(2) {
+            text += &format!("{:+}\n", pair[1] - pair[0]);
+        }
+        text
+    }
+
+    fn oracle1(numbers: &Vec<i64>) -> Result<i64> {
+        let mut frequency = 0;
+        for n in numbers {
+            frequency += n;
+        }
+        Ok(frequency)
+    }
+
+    fn oracle2(numbers: &Vec<i64>) -> Result<i64> {
+        let mut frequency = 0;
+        let mut seen = HashSet::new();
+        // a repeat within 10^7 changes or not at all, for generated input
+        for n in numbers.iter().cycle().take(10_000_000) {
+            if !seen.insert(frequency) {
+                return Ok(frequency);
+            }
+            frequency += n;
+        }
+        Err("No repeated frequency found".into())
+    }
+}
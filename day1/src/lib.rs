@@ -0,0 +1,347 @@
+#![allow(dead_code)]
+use std::collections::HashSet;
+use std::io::BufRead;
+
+use aoc_common::{FromLine, Input, ParseError, Result, Solution, Variant};
+
+pub mod gen;
+
+pub const DEFAULT_INPUT: &str = concat!(env!("CARGO_MANIFEST_DIR"), "/data/input.txt");
+
+// Changes are 32 bit, the sums of them are kept in 64 bits so they can't overflow.
+fn parse_frequency(line: &str) -> std::result::Result<i64, ParseError> {
+    i32::from_line(line).map(i64::from)
+}
+
+// iterator with error, Result Sum version
+pub fn sum_up_file_map(reader: impl BufRead) -> Result<i64> {
+    reader
+        .lines()
+        .map(|x| Ok(i64::from(i32::from_line(&x?)?)))
+        //.map(|res| res.map(|x| x + 1))
+        .sum()
+}
+
+fn sum_up_file_fold(reader: impl BufRead) -> Result<i64> {
+    reader
+        .lines()
+        .try_fold(0, |acc, line| Ok(acc + i64::from(i32::from_line(&line?)?)))
+}
+
+fn sum_up_file_loop(reader: impl BufRead) -> Result<i64> {
+    let mut sum = 0;
+    for line in reader.lines() {
+        let x = i64::from(i32::from_line(&line?)?);
+        sum += x;
+    }
+    Ok(sum)
+}
+
+pub fn read_frequencies(input: &Input) -> Result<Vec<i64>> {
+    aoc_common::parse_lines(input, parse_frequency)
+}
+
+pub fn div_rem<T: std::ops::Div<Output = T> + std::ops::Rem<Output = T> + Copy>(
+    x: T,
+    y: T,
+) -> (T, T) {
+    let quot = x / y;
+    let rem = x % y;
+    (quot, rem)
+}
+
+fn find_first_repeat_1(numbers: &[i64]) -> Option<i64> {
+    // every pass shifts the frequencies by the total, so after enough passes
+    // to shift them past the range of the first one nothing can repeat
+    let (min, max) = numbers
+        .iter()
+        .scan(0, |sum, n| {
+            *sum += n;
+            Some(*sum)
+        })
+        .fold((0, 0), |(min, max), f| (f.min(min), f.max(max)));
+    let total: i64 = numbers.iter().sum();
+    let passes = match total {
+        0 => 2,
+        _ => ((max - min) / total.abs()) as usize + 2,
+    };
+    let mut sum = 0;
+    let mut seen_numbers = HashSet::new();
+    for n in numbers
+        .iter()
+        .cycle()
+        .take(numbers.len().saturating_mul(passes))
+    {
+        //println!("{} {}", n, sum);
+        if seen_numbers.contains(&sum) {
+            return Some(sum);
+        //println!("First twice = {}", sum);
+        //break;
+        } else {
+            seen_numbers.insert(sum);
+        }
+        sum += n;
+    }
+    None
+}
+
+pub fn find_first_repeat_2(numbers: &[i64]) -> Option<i64> {
+    // frequencies during the first pass, starting from 0. Frequency number
+    // q * n + j is then freqs[j] + q * total_sum.
+    let freqs: Vec<i64> = std::iter::once(0)
+        .chain(numbers.iter().scan(0, |sum, n| {
+            *sum += n;
+            Some(*sum)
+        }))
+        .take(numbers.len())
+        .collect();
+
+    let total_sum: i64 = numbers.iter().sum();
+    let mut rep_val = None;
+    let mut rep_ind = usize::MAX;
+
+    for i in 0..freqs.len() {
+        for j in 0..freqs.len() {
+            if i == j {
+                continue;
+            }
+            // does frequency j, q passes later, repeat frequency i?
+            let diff = freqs[i] - freqs[j];
+            let q = match total_sum {
+                0 if diff == 0 => 0,
+                0 => continue,
+                _ => match div_rem(diff, total_sum) {
+                    (q, 0) if q >= 0 => q as usize,
+                    _ => continue,
+                },
+            };
+            if q == 0 && j < i {
+                continue;
+            }
+            let ind = numbers.len() * q + j;
+            if ind < rep_ind {
+                rep_ind = ind;
+                rep_val = Some(freqs[i]);
+            }
+        }
+    }
+    if total_sum == 0 && !numbers.is_empty() {
+        // everything repeats after one pass, starting with the initial 0
+        return rep_val.or(Some(0));
+    }
+    rep_val
+}
+
+pub struct Day1;
+
+impl Solution for Day1 {
+    const DAY: u32 = 1;
+    const TITLE: &'static str = "Chronal Calibration";
+    const DEFAULT_INPUT: &'static str = DEFAULT_INPUT;
+
+    type Parsed = Vec<i64>;
+    type Answer1 = i64;
+    type Answer2 = i64;
+
+    fn parse(input: &Input) -> Result<Vec<i64>> {
+        read_frequencies(input)
+    }
+
+    fn part1(numbers: &Vec<i64>) -> Result<i64> {
+        Ok(numbers.iter().sum())
+    }
+
+    fn part2(numbers: &Vec<i64>) -> Result<i64> {
+        Ok(find_first_repeat_2(numbers).ok_or("There is no repeated sum")?)
+    }
+
+    const VARIANTS: &'static [Variant<Vec<i64>>] = &[
+        Variant {
+            name: "map",
+            part: 1,
+            solve: |input, _| Ok(sum_up_file_map(input.open()?)?.to_string()),
+        },
+        Variant {
+            name: "fold",
+            part: 1,
+            solve: |input, _| Ok(sum_up_file_fold(input.open()?)?.to_string()),
+        },
+        Variant {
+            name: "loop",
+            part: 1,
+            solve: |input, _| Ok(sum_up_file_loop(input.open()?)?.to_string()),
+        },
+        Variant {
+            name: "repeat1",
+            part: 2,
+            solve: |_, numbers| {
+                let sum = find_first_repeat_1(numbers).ok_or("There is no repeated sum")?;
+                Ok(sum.to_string())
+            },
+        },
+        Variant {
+            name: "repeat2",
+            part: 2,
+            solve: |_, numbers| Ok(Day1::part2(numbers)?.to_string()),
+        },
+    ];
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use proptest::prelude::*;
+
+    fn parse(example: &str) -> Vec<i64> {
+        Day1::parse(&Input::text("example", example.replace(", ", "\n"))).unwrap()
+    }
+
+    #[test]
+    fn example_part1() {
+        let cases = [
+            ("+1, -2, +3, +1", 3),
+            ("+1, +1, +1", 3),
+            ("+1, +1, -2", 0),
+            ("-1, -2, -3", -6),
+        ];
+        for &(example, expected) in cases.iter() {
+            assert_eq!(
+                Day1::part1(&parse(example)).unwrap(),
+                expected,
+                "{}",
+                example
+            );
+        }
+    }
+
+    #[test]
+    fn example_part2() {
+        let cases = [
+            ("+1, -2, +3, +1", 2),
+            ("+1, -1", 0),
+            ("+3, +3, +4, -2, -4", 10),
+            ("-6, +3, +8, +5, -6", 5),
+            ("+7, +7, -2, -7, -4", 14),
+        ];
+        for &(example, expected) in cases.iter() {
+            assert_eq!(
+                Day1::part2(&parse(example)).unwrap(),
+                expected,
+                "{}",
+                example
+            );
+            assert_eq!(find_first_repeat_1(&parse(example)), Some(expected));
+        }
+    }
+
+    #[test]
+    fn repeats_initial_frequency() {
+        // 0 comes back in the second pass, and with no drift in the first
+        let cases = [("-1, +2", 0), ("+1, +2, -3", 0), ("+2, -2", 0)];
+        for &(example, expected) in cases.iter() {
+            let numbers = parse(example);
+            assert_eq!(find_first_repeat_2(&numbers), Some(expected), "{}", example);
+            assert_eq!(find_first_repeat_1(&numbers), Some(expected), "{}", example);
+        }
+    }
+
+    #[test]
+    fn no_repeat() {
+        let numbers = parse("+1, +1");
+        for variant in Day1::VARIANTS {
+            let answer = (variant.solve)(&input(&numbers), &numbers);
+            match variant.part {
+                1 => assert_eq!(answer.unwrap(), "2", "{}", variant.name),
+                _ => assert!(answer.is_err(), "{}", variant.name),
+            }
+        }
+        assert!(Day1::part2(&numbers).is_err());
+    }
+
+    #[test]
+    fn line_endings() {
+        let crlf = Input::text("crlf", "\u{feff}+1\r\n-2 \r\n+3\r\n+1\r\n\r\n");
+        assert_eq!(Day1::part1(&Day1::parse(&crlf).unwrap()).unwrap(), 3);
+        aoc_common::cross_check::<Day1>(&crlf).unwrap();
+    }
+
+    #[test]
+    fn variants_agree() {
+        aoc_common::cross_check::<Day1>(&Input::text("example", "+1\n-2\n+3\n+1\n")).unwrap();
+        aoc_common::cross_check::<Day1>(&Input::file(DEFAULT_INPUT)).unwrap();
+    }
+
+    #[test]
+    fn repeat_in_chosen_pass() {
+        for repeat_pass in 0..4 {
+            let params = gen::Params {
+                repeat_pass,
+                ..Default::default()
+            };
+            let input = aoc_common::gen::generate::<Day1>(1, 20, &params);
+            let numbers = Day1::parse(&input).unwrap();
+            let total: i64 = numbers.iter().sum();
+            let repeat = Day1::part2(&numbers).unwrap();
+            let (first_pass, _) = numbers.iter().fold((vec![0], 0), |(mut v, f), n| {
+                v.push(f + n);
+                (v, f + n)
+            });
+            assert!(first_pass.contains(&(repeat - repeat_pass as i64 * total)));
+        }
+    }
+
+    fn frequencies() -> impl Strategy<Value = Vec<i64>> {
+        prop::collection::vec(-20i64..=20, 1..20)
+    }
+
+    fn input(numbers: &[i64]) -> Input {
+        let lines: Vec<String> = numbers.iter().map(|n| format!("{:+}", n)).collect();
+        Input::text("generated", lines.join("\n"))
+    }
+
+    #[test]
+    fn generated_tiny() {
+        for size in 0..2 {
+            let input = aoc_common::gen::generate::<Day1>(7, size, &Default::default());
+            aoc_common::gen::check::<Day1>(&input).unwrap();
+        }
+    }
+
+    proptest! {
+        #[test]
+        fn changes_round_trip(change: i32) {
+            prop_assert_eq!(parse_frequency(&format!("{:+}", change)), Ok(i64::from(change)));
+            prop_assert_eq!(parse_frequency(&format!(" {} ", change)), Ok(i64::from(change)));
+        }
+
+        #[test]
+        fn sum_variants_agree(numbers in frequencies()) {
+            let input = input(&numbers);
+            let expected: i64 = numbers.iter().sum();
+            prop_assert_eq!(sum_up_file_map(input.open().unwrap()).unwrap(), expected);
+            prop_assert_eq!(sum_up_file_fold(input.open().unwrap()).unwrap(), expected);
+            prop_assert_eq!(sum_up_file_loop(input.open().unwrap()).unwrap(), expected);
+        }
+
+        #[test]
+        fn generated_matches_oracle(seed: u64, size in 1usize..200, repeat_pass in 0u64..20) {
+            let params = gen::Params { repeat_pass, ..Default::default() };
+            let input = aoc_common::gen::generate::<Day1>(seed, size, &params);
+            let checked = aoc_common::gen::check::<Day1>(&input);
+            prop_assert!(checked.is_ok(), "{}", checked.unwrap_err());
+        }
+
+        #[test]
+        fn repeats_agree(numbers in frequencies()) {
+            prop_assert_eq!(find_first_repeat_1(&numbers), find_first_repeat_2(&numbers));
+        }
+
+        #[test]
+        fn variants_agree_on_generated(numbers in frequencies()) {
+            // part 2 fails without a repeat, leaving nothing to compare
+            prop_assume!(find_first_repeat_2(&numbers).is_some());
+            let checked = aoc_common::cross_check::<Day1>(&input(&numbers));
+            prop_assert!(checked.is_ok(), "{}", checked.unwrap_err());
+        }
+    }
+}
@@ -1,22 +1,49 @@
 #[macro_use]
 extern crate bencher;
-extern crate aoc;
 
-use bencher::Bencher;
-use std::path::Path;
+use aoc_common::{bench, Input};
+use bencher::{Bencher, TestDescAndFn};
+use day2::Day2;
 
-fn perf_2(b: &mut Bencher) {
-    let box_ids = aoc::read_box_ids(Path::new("../input.txt")).unwrap();
-    b.iter(|| {
-        aoc::find_matching_boxes2(&box_ids);
-    })
-}
-fn perf_3(b: &mut Bencher) {
-    let box_ids = aoc::read_box_ids(Path::new("../input.txt")).unwrap();
-    b.iter(|| {
-        aoc::find_matching_boxes3(&box_ids);
-    })
+fn real() -> Input {
+    bench::load(day2::DEFAULT_INPUT)
 }
 
-benchmark_group!(benches, perf_2, perf_3);
-benchmark_main!(benches);
+// Generated input with 1000 box IDs.
+fn scaled() -> Input {
+    aoc_common::gen::generate::<Day2>(1, 1000, &Default::default())
+}
+
+fn parse(b: &mut Bencher) {
+    bench::parse::<Day2>(b, &real())
+}
+fn part1(b: &mut Bencher) {
+    bench::part1::<Day2>(b, &real())
+}
+fn part2(b: &mut Bencher) {
+    bench::part2::<Day2>(b, &real())
+}
+fn parse_scaled(b: &mut Bencher) {
+    bench::parse::<Day2>(b, &scaled())
+}
+fn part1_scaled(b: &mut Bencher) {
+    bench::part1::<Day2>(b, &scaled())
+}
+fn part2_scaled(b: &mut Bencher) {
+    bench::part2::<Day2>(b, &scaled())
+}
+
+fn variants() -> Vec<TestDescAndFn> {
+    bench::variants::<Day2>(&real())
+}
+
+benchmark_group!(
+    benches,
+    parse,
+    part1,
+    part2,
+    parse_scaled,
+    part1_scaled,
+    part2_scaled
+);
+benchmark_main!(benches, variants);
@@ -0,0 +1,102 @@
+//! Box ID sets with exactly one planted pair of IDs that differ in a single
+//! position.
+use std::collections::HashMap;
+
+use aoc_common::{Generator, Result, Rng};
+
+use crate::Day2;
+
+pub struct Params {
+    /// Length of every ID.
+    pub len: usize,
+    /// Number of different letters, starting from `a`.
+    pub letters: u8,
+}
+
+impl Default for Params {
+    fn default() -> Params {
+        Params {
+            len: 26,
+            letters: 26,
+        }
+    }
+}
+
+fn differences(a: &[u8], b: &[u8]) -> usize {
+    a.iter().zip(b).filter(|(a, b)| a != b).count()
+}
+
+impl Generator for Day2 {
+    type Params = Params;
+
+    fn generate(rng: &mut Rng, size: usize, params: &Params) -> String {
+        let letters = u64::from(params.letters.clamp(2, 26));
+        let random_id = |rng: &mut Rng| -> Vec<u8> {
+            (0..params.len.max(1))
+                .map(|_| b'a' + rng.below(letters) as u8)
+                .collect()
+        };
+
+        // IDs that differ in at least two positions from all the others, the
+        // first always kept
+        let size = size.max(2);
+        let mut ids: Vec<Vec<u8>> = Vec::new();
+        let mut attempts = 0;
+        while ids.len() < size - 1 && attempts < 100 * size {
+            attempts += 1;
+            let id = random_id(rng);
+            if ids.iter().all(|other| differences(&id, other) >= 2) {
+                ids.push(id);
+            }
+        }
+
+        // and a copy of one of them with one letter changed, dropping any
+        // others that are close to the copy too
+        let original = rng.pick(&ids).clone();
+        let mut twin = original.clone();
+        let pos = rng.below(twin.len() as u64) as usize;
+        let shift = 1 + rng.below(letters - 1) as u8;
+        twin[pos] = b'a' + (twin[pos] - b'a' + shift) % letters as u8;
+        ids.retain(|id| *id == original || differences(&twin, id) >= 2);
+        let pos = rng.below(ids.len() as u64 + 1) as usize;
+        ids.insert(pos, twin);
+
+        let mut text = String::new();
+        for id in ids {
+            text += &String::from_utf8(id).unwrap();
+            text.push('\n');
+        }
+        text
+    }
+
+    fn oracle1(box_ids: &Vec<Vec<u8>>) -> Result<usize> {
+        let mut twos = 0;
+        let mut threes = 0;
+        for id in box_ids {
+            let mut counts = HashMap::new();
+            for letter in id {
+                *counts.entry(letter).or_insert(0) += 1;
+            }
+            twos += counts.values().any(|&n| n == 2) as usize;
+            threes += counts.values().any(|&n| n == 3) as usize;
+        }
+        Ok(twos * threes)
+    }
+
+    fn oracle2(box_ids: &Vec<Vec<u8>>) -> Result<String> {
+        let mut found = Vec::new();
+        for a in box_ids {
+            for b in box_ids {
+                if a < b && differences(a, b) == 1 {
+                    let common = a.iter().zip(b).filter(|(a, b)| a == b);
+                    found.push(common.map(|(&a, _)| a as char).collect::<String>());
+                }
+            }
+        }
+        match found.len() {
+            1 => Ok(found.pop().unwrap()),
+            0 => Err("No matching boxes found".into()),
+            _ => Err("More than one pair of matching boxes".into()),
+        }
+    }
+}
@@ -1,23 +1,18 @@
 #![allow(dead_code)]
-use std::boxed::Box;
-use std::error;
-use std::fs::File;
-use std::io::{BufRead, BufReader};
-use std::path::Path;
-
-pub type Result<T> = ::std::result::Result<T, Box<dyn error::Error>>;
-
-pub fn read_box_ids(filename: &Path) -> Result<Vec<Vec<u8>>> {
-    let f = File::open(filename).map_err(|e| {
-        format!("Unable to open input file '{}': {}", filename.display(), e).to_string()
-    })?;
-    let f = BufReader::new(f);
-    let box_ids: Vec<String> = f.lines().collect::<std::result::Result<_, _>>()?;
+
+use aoc_common::{par, Input, Result, Solution, Variant};
+
+pub mod gen;
+
+pub const DEFAULT_INPUT: &str = concat!(env!("CARGO_MANIFEST_DIR"), "/data/input.txt");
+
+pub fn read_box_ids(input: &Input) -> Result<Vec<Vec<u8>>> {
+    let box_ids = aoc_common::read_lines(input)?;
     let box_ids: Vec<Vec<u8>> = box_ids.into_iter().map(|s| s.into_bytes()).collect();
     Ok(box_ids)
 }
 
-pub fn count_ids(ids: &Vec<Vec<u8>>) -> (usize, usize) {
+pub fn count_ids(ids: &[Vec<u8>]) -> (usize, usize) {
     let mut nums = (0, 0);
 
     for id in ids {
@@ -27,19 +22,15 @@ pub fn count_ids(ids: &Vec<Vec<u8>>) -> (usize, usize) {
             histogram[*b as usize] += 1;
         }
 
-        for x in histogram.iter() {
-            if *x == 2i32 {
-                nums.0 += 1;
-            } else if *x == 3i32 {
-                nums.1 += 1
-            }
-        }
+        // each id counts at most once, however many letters repeat
+        nums.0 += histogram.contains(&2) as usize;
+        nums.1 += histogram.contains(&3) as usize;
     }
     nums
 }
 
 // SLOW: 1975 ns
-pub fn find_matching_boxes1(ids: &Vec<Vec<u8>>) -> Option<Vec<u8>> {
+pub fn find_matching_boxes1(ids: &[Vec<u8>]) -> Option<Vec<u8>> {
     for i in 0..ids.len() {
         for j in i + 1..ids.len() {
             let tups: Vec<_> = ids[i].iter().zip(&ids[j]).map(|(&a, &b)| (a, b)).collect();
@@ -52,7 +43,7 @@ pub fn find_matching_boxes1(ids: &Vec<Vec<u8>>) -> Option<Vec<u8>> {
 }
 
 // FASTEST: 177 ns
-pub fn find_matching_boxes2(ids: &Vec<Vec<u8>>) -> Option<Vec<u8>> {
+pub fn find_matching_boxes2(ids: &[Vec<u8>]) -> Option<Vec<u8>> {
     for i in 0..ids.len() {
         for j in i + 1..ids.len() {
             let tups = ids[i].iter().zip(&ids[j]).map(|(&a, &b)| (a, b));
@@ -65,7 +56,7 @@ pub fn find_matching_boxes2(ids: &Vec<Vec<u8>>) -> Option<Vec<u8>> {
 }
 
 // C: 380 ns
-pub fn find_matching_boxes3(ids: &Vec<Vec<u8>>) -> Option<Vec<u8>> {
+pub fn find_matching_boxes3(ids: &[Vec<u8>]) -> Option<Vec<u8>> {
     for i in 0..ids.len() {
         for j in i + 1..ids.len() {
             let mut num_diffs = 0;
@@ -78,9 +69,187 @@ pub fn find_matching_boxes3(ids: &Vec<Vec<u8>>) -> Option<Vec<u8>> {
                 }
             }
             if num_diffs == 1 {
-                return Some(ids[i].clone());
+                let common = ids[i].iter().zip(&ids[j]).filter(|(a, b)| a == b);
+                return Some(common.map(|(&a, _)| a).collect());
             }
         }
     }
     None
 }
+
+/// The letters `a` and `b` have in common, if they differ in exactly one.
+fn one_letter_apart(a: &[u8], b: &[u8]) -> Option<Vec<u8>> {
+    let mut diffs = a.iter().zip(b).filter(|(a, b)| a != b);
+    match (diffs.next(), diffs.next()) {
+        (Some(_), None) => Some(
+            a.iter()
+                .zip(b)
+                .filter(|(a, b)| a == b)
+                .map(|(&a, _)| a)
+                .collect(),
+        ),
+        _ => None,
+    }
+}
+
+/// Same search as the others, with the outer loop spread over all cores
+/// when the `parallel` feature is on. Finds the same pair as the sequential
+/// versions.
+pub fn find_matching_boxes_par(ids: &[Vec<u8>]) -> Option<Vec<u8>> {
+    let indices: Vec<usize> = (0..ids.len()).collect();
+    par::find_map_first(&indices, |&i| {
+        ids[i + 1..]
+            .iter()
+            .find_map(|other| one_letter_apart(&ids[i], other))
+    })
+}
+
+fn common_letters(common: Option<Vec<u8>>) -> Result<String> {
+    let common = common.ok_or("No matching boxes found")?;
+    Ok(String::from_utf8(common)?)
+}
+
+pub struct Day2;
+
+impl Solution for Day2 {
+    const DAY: u32 = 2;
+    const TITLE: &'static str = "Inventory Management System";
+    const DEFAULT_INPUT: &'static str = DEFAULT_INPUT;
+
+    type Parsed = Vec<Vec<u8>>;
+    type Answer1 = usize;
+    type Answer2 = String;
+
+    fn parse(input: &Input) -> Result<Vec<Vec<u8>>> {
+        read_box_ids(input)
+    }
+
+    fn part1(box_ids: &Vec<Vec<u8>>) -> Result<usize> {
+        let (num_2, num_3) = count_ids(box_ids);
+        Ok(num_2 * num_3)
+    }
+
+    fn part2(box_ids: &Vec<Vec<u8>>) -> Result<String> {
+        common_letters(find_matching_boxes_par(box_ids))
+    }
+
+    const VARIANTS: &'static [Variant<Vec<Vec<u8>>>] = &[
+        Variant {
+            name: "boxes1",
+            part: 2,
+            solve: |_, box_ids| common_letters(find_matching_boxes1(box_ids)),
+        },
+        Variant {
+            name: "boxes2",
+            part: 2,
+            solve: |_, box_ids| common_letters(find_matching_boxes2(box_ids)),
+        },
+        Variant {
+            name: "boxes3",
+            part: 2,
+            solve: |_, box_ids| common_letters(find_matching_boxes3(box_ids)),
+        },
+    ];
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use proptest::prelude::*;
+
+    fn parse(example: &[&str]) -> Vec<Vec<u8>> {
+        Day2::parse(&Input::text("example", example.join("\n"))).unwrap()
+    }
+
+    #[test]
+    fn example_part1() {
+        let box_ids = parse(&[
+            "abcdef", "bababc", "abbcde", "abcccd", "aabcdd", "abcdee", "ababab",
+        ]);
+        assert_eq!(count_ids(&box_ids), (4, 3));
+        assert_eq!(Day2::part1(&box_ids).unwrap(), 12);
+    }
+
+    #[test]
+    fn ids_count_once() {
+        // two doubled letters and two tripled ones still count as one ID each
+        let box_ids = parse(&["aabbcccddd", "abcdef", "aabcd"]);
+        assert_eq!(count_ids(&box_ids), (2, 1));
+        assert_eq!(Day2::part1(&box_ids).unwrap(), 2);
+    }
+
+    #[test]
+    fn example_part2() {
+        let box_ids = parse(&[
+            "abcde", "fghij", "klmno", "pqrst", "fguij", "axcye", "wvxyz",
+        ]);
+        assert_eq!(Day2::part2(&box_ids).unwrap(), "fgij");
+    }
+
+    #[test]
+    fn line_endings() {
+        let ids = ["abcdef", "bababc", "abbcde", "abcccd"];
+        let crlf = format!("\u{feff}{}\r\n\r\n", ids.join("\r\n"));
+        let box_ids = Day2::parse(&Input::text("crlf", crlf)).unwrap();
+        assert_eq!(box_ids, parse(&ids));
+        assert_eq!(count_ids(&box_ids), (2, 2));
+    }
+
+    #[test]
+    fn variants_agree() {
+        let example = [
+            "abcde", "fghij", "klmno", "pqrst", "fguij", "axcye", "wvxyz",
+        ];
+        aoc_common::cross_check::<Day2>(&Input::text("example", example.join("\n"))).unwrap();
+        aoc_common::cross_check::<Day2>(&Input::file(DEFAULT_INPUT)).unwrap();
+    }
+
+    /// Sets of equally long IDs over a small alphabet, so that most sets have
+    /// a pair of IDs differing in exactly one position.
+    fn box_ids() -> impl Strategy<Value = Vec<Vec<u8>>> {
+        (1usize..8).prop_flat_map(|len| {
+            let id = prop::collection::vec(prop::sample::select(b"abc".to_vec()), len);
+            prop::collection::vec(id, 0..16)
+        })
+    }
+
+    #[test]
+    fn parallel_matches_sequential() {
+        let input = aoc_common::gen::generate::<Day2>(7, 2000, &Default::default());
+        let ids = Day2::parse(&input).unwrap();
+        let found = find_matching_boxes_par(&ids);
+        assert_eq!(found, par::sequential(|| find_matching_boxes_par(&ids)));
+        assert_eq!(found, find_matching_boxes3(&ids));
+    }
+
+    #[test]
+    fn generated_tiny() {
+        for size in 0..2 {
+            let input = aoc_common::gen::generate::<Day2>(7, size, &Default::default());
+            aoc_common::gen::check::<Day2>(&input).unwrap();
+        }
+    }
+
+    proptest! {
+        #[test]
+        fn matching_variants_agree(box_ids in box_ids()) {
+            let expected = find_matching_boxes1(&box_ids);
+            prop_assert_eq!(find_matching_boxes2(&box_ids), expected.clone());
+            prop_assert_eq!(find_matching_boxes3(&box_ids), expected);
+        }
+
+        #[test]
+        fn generated_matches_oracle(seed: u64, size in 1usize..100, len in 1usize..30, letters in 2u8..27) {
+            let params = gen::Params { len, letters };
+            let input = aoc_common::gen::generate::<Day2>(seed, size, &params);
+            let checked = aoc_common::gen::check::<Day2>(&input);
+            prop_assert!(checked.is_ok(), "{}", checked.unwrap_err());
+        }
+
+        #[test]
+        fn counts_are_bounded(box_ids in box_ids()) {
+            let (num_2, num_3) = count_ids(&box_ids);
+            prop_assert!(num_2 <= box_ids.len() && num_3 <= box_ids.len());
+        }
+    }
+}
@@ -1,22 +1,45 @@
 #[macro_use]
 extern crate bencher;
-extern crate aoc;
 
+use aoc_common::{bench, Input};
 use bencher::Bencher;
+use day3::Day3;
 
-let box_ids = aoc::read_box_ids("../input.txt").unwrap();
+fn real() -> Input {
+    bench::load(day3::DEFAULT_INPUT)
+}
+
+// Generated input with 4000 claims.
+fn scaled() -> Input {
+    aoc_common::gen::generate::<Day3>(1, 4000, &Default::default())
+}
 
-fn perf_2(b: &mut Bencher) {
-    let box_ids = aoc::read_box_ids("../input.txt").unwrap();
-    b.iter(|| {
-        aoc::find_matching_boxes2(&box_ids);
-    })
+fn parse(b: &mut Bencher) {
+    bench::parse::<Day3>(b, &real())
+}
+fn part1(b: &mut Bencher) {
+    bench::part1::<Day3>(b, &real())
+}
+fn part2(b: &mut Bencher) {
+    bench::part2::<Day3>(b, &real())
+}
+fn parse_scaled(b: &mut Bencher) {
+    bench::parse::<Day3>(b, &scaled())
+}
+fn part1_scaled(b: &mut Bencher) {
+    bench::part1::<Day3>(b, &scaled())
 }
-fn perf_3(b: &mut Bencher) {
-    b.iter(|| {
-        aoc::find_matching_boxes3(&box_ids);
-    })
+fn part2_scaled(b: &mut Bencher) {
+    bench::part2::<Day3>(b, &scaled())
 }
 
-benchmark_group!(benches, perf_2, perf_3);
+benchmark_group!(
+    benches,
+    parse,
+    part1,
+    part2,
+    parse_scaled,
+    part1_scaled,
+    part2_scaled
+);
 benchmark_main!(benches);
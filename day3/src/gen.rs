@@ -0,0 +1,103 @@
+//! Claims on a fabric sized for a given overlap density, with exactly one
+//! claim that doesn't overlap any other.
+use std::collections::HashMap;
+
+use aoc_common::{Generator, Point, Rect, Result, Rng};
+
+use crate::{Claim, Day3};
+
+pub struct Params {
+    /// Largest width and height of a claim.
+    pub max_side: u32,
+    /// Total area of all claims relative to the area of the fabric.
+    pub density: f64,
+}
+
+impl Default for Params {
+    fn default() -> Params {
+        Params {
+            max_side: 30,
+            density: 0.5,
+        }
+    }
+}
+
+fn overlaps(a: &Claim, b: &Claim) -> bool {
+    a.rect.intersection(&b.rect).is_some()
+}
+
+impl Generator for Day3 {
+    type Params = Params;
+
+    fn generate(rng: &mut Rng, size: usize, params: &Params) -> String {
+        let max_side = params.max_side.max(1);
+        let side = f64::from(max_side + 1) / 2.0;
+        let area = size as f64 * side * side / params.density.max(0.01);
+        let fabric = (area.sqrt() as u64).max(2 * u64::from(max_side));
+
+        let random_claim = |rng: &mut Rng| {
+            let w = rng.range(1, i64::from(max_side)) as usize;
+            let h = rng.range(1, i64::from(max_side)) as usize;
+            let x = rng.below(fabric - w as u64) as isize;
+            let y = rng.below(fabric - h as u64) as isize;
+            let rect = Rect::new(x, y, w, h);
+            Claim { id: 0, rect }
+        };
+
+        let alone = random_claim(rng);
+        let mut claims = Vec::new();
+        while claims.len() + 1 < size.max(1) {
+            let claim = random_claim(rng);
+            if !overlaps(&claim, &alone) {
+                claims.push(claim);
+            }
+        }
+        // every other claim without overlap gets a tiny one on top of it
+        let mut extra = Vec::new();
+        for (i, c) in claims.iter().enumerate() {
+            let (before, after) = (&claims[..i], &claims[i + 1..]);
+            if !before.iter().chain(after).any(|other| overlaps(c, other)) {
+                let Point { x, y } = c.rect.max();
+                extra.push(Claim {
+                    id: 0,
+                    rect: Rect::new(x, y, 1, 1),
+                });
+            }
+        }
+        claims.append(&mut extra);
+        let pos = rng.below(claims.len() as u64 + 1) as usize;
+        claims.insert(pos, alone);
+
+        // IDs are handed out in the order the claims are written
+        let mut text = String::new();
+        for (id, c) in claims.iter().enumerate() {
+            let id = id as u32 + 1;
+            text += &format!("{}\n", Claim { id, rect: c.rect });
+        }
+        text
+    }
+
+    fn oracle1(claims: &Vec<Claim>) -> Result<usize> {
+        let mut cells = HashMap::new();
+        for c in claims {
+            for x in c.rect.xs() {
+                for y in c.rect.ys() {
+                    *cells.entry((x, y)).or_insert(0) += 1;
+                }
+            }
+        }
+        Ok(cells.values().filter(|&&n| n > 1).count())
+    }
+
+    fn oracle2(claims: &Vec<Claim>) -> Result<u32> {
+        let alone: Vec<&Claim> = claims
+            .iter()
+            .filter(|c| !claims.iter().any(|o| o.id != c.id && overlaps(c, o)))
+            .collect();
+        match alone[..] {
+            [claim] => Ok(claim.id),
+            [] => Err("Every claim overlaps another one".into()),
+            _ => Err("More than one claim without overlap".into()),
+        }
+    }
+}
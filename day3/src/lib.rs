@@ -1,78 +1,194 @@
 #![allow(dead_code)]
-use std::boxed::Box;
-use std::error;
-use std::fs::File;
-use std::io::{BufRead, BufReader};
-use std::path::Path;
+use std::fmt;
 
-#[macro_use]
-extern crate scan_fmt;
-extern crate nalgebra as na;
+use aoc_common::parse::fields;
+use aoc_common::{grid, FromLine, Grid, Input, ParseError, Rect, Result, Solution};
 
-pub type Result<T> = ::std::result::Result<T, Box<dyn error::Error>>;
+pub mod gen;
+pub mod render;
 
-#[derive(Debug)]
+pub const DEFAULT_INPUT: &str = concat!(env!("CARGO_MANIFEST_DIR"), "/data/input.txt");
+
+#[derive(Debug, PartialEq)]
 pub struct Claim {
     id: u32,
-    x: u32,
-    y: u32,
-    w: u32,
-    h: u32,
+    rect: Rect,
 }
 
-pub fn read_claims(filename: &Path) -> Result<Vec<Claim>> {
-    let file = File::open(filename).map_err(|e| {
-        format!("Unable to open input file '{}': {}", filename.display(), e).to_string()
-    })?;
-    let file = BufReader::new(file);
-    let mut claims = Vec::new();
-    for line in file.lines() {
-        let line = line?;
-        match scan_fmt!(&line, "#{d} @ {d},{d}: {d}x{d}", u32, u32, u32, u32, u32) {
-            Ok((id, x, y, w, h)) => claims.push(Claim { id, x, y, w, h }),
-            Err(e) => return Err(e.into()),
-        }
+impl FromLine for Claim {
+    fn from_line(line: &str) -> std::result::Result<Claim, ParseError> {
+        let mut fields = fields("#{} @ {},{}: {}x{}", line)?;
+        let id = fields.number()?;
+        let x: u32 = fields.number()?;
+        let y: u32 = fields.number()?;
+        let w: u32 = fields.number()?;
+        let h: u32 = fields.number()?;
+        let rect = Rect::new(x as isize, y as isize, w as usize, h as usize);
+        Ok(Claim { id, rect })
+    }
+}
+
+impl fmt::Display for Claim {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let r = self.rect;
+        write!(
+            f,
+            "#{} @ {},{}: {}x{}",
+            self.id, r.x, r.y, r.width, r.height
+        )
     }
-    Ok(claims)
 }
 
-fn find_fabric_size(claims: &[Claim]) -> Option<(usize, usize)> {
-    let max_right = claims.iter().max_by_key(|c| c.x + c.w)?;
-    let max_bottom = claims.iter().max_by_key(|c| c.y + c.h)?;
-    Some((
-        (max_right.x + max_right.w) as usize,
-        (max_bottom.y + max_bottom.h) as usize,
-    ))
+pub fn read_claims(input: &Input) -> Result<Vec<Claim>> {
+    aoc_common::read_records(input)
 }
 
-pub fn layout_claims(claims: &[Claim]) -> na::DMatrix<i32> {
-    let (fw, fh) = find_fabric_size(claims).unwrap();
-    println!("size: {}x{}", fw, fh);
+/// The fabric from the top left corner to the furthest edges of the claims,
+/// or `None` without claims.
+pub fn find_fabric(claims: &[Claim]) -> Option<Rect> {
+    let right = claims.iter().map(|c| c.rect.right()).max()?;
+    let bottom = claims.iter().map(|c| c.rect.bottom()).max()?;
+    Some(Rect::new(0, 0, right as usize, bottom as usize))
+}
+
+pub fn layout_claims(claims: &[Claim]) -> Result<Grid<i32>> {
+    let fabric = find_fabric(claims).ok_or("There are no claims")?;
+    grid::check_size(fabric, "fabric")?;
+    let claimed = claims
+        .iter()
+        .fold(0, |sum: usize, c| sum.saturating_add(c.rect.area()));
+    grid::check_work(fabric.area().saturating_add(claimed), "fabric")?;
+    log::debug!("fabric size: {}x{}", fabric.width, fabric.height);
 
-    let mut fabric = na::DMatrix::<i32>::zeros(fh, fw);
+    let mut fabric = Grid::new(fabric, 0);
     for c in claims {
-        let (cx, cy) = (c.x as usize, c.y as usize);
-        let (cw, ch) = (c.w as usize, c.h as usize);
-        fabric.slice_mut((cy, cx), (ch, cw)).apply(|x| x + 1);
+        fabric.view_mut(c.rect).iter_mut().for_each(|x| *x += 1);
     }
-    fabric
+    Ok(fabric)
 }
 
-pub fn find_overallocated_cells(fabric: &na::DMatrix<i32>) -> usize {
-    fabric.iter().map(|&x| (x > 1) as usize).sum()
+pub fn find_overallocated_cells(fabric: &Grid<i32>) -> usize {
+    fabric.values().map(|&x| (x > 1) as usize).sum()
 }
 
-pub fn find_nonoverlappig_rect(claims: &[Claim], fabric: &na::DMatrix<i32>) -> u32 {
+pub fn find_nonoverlappig_rect(claims: &[Claim], fabric: &Grid<i32>) -> Option<u32> {
     for c in claims {
-        let (cx, cy) = (c.x as usize, c.y as usize);
-        let (cw, ch) = (c.w as usize, c.h as usize);
-        if fabric
-            .slice((cy, cx), (ch, cw))
-            .map(|x| x == 1)
-            .fold(true, |acc, x| acc & x)
-        {
-            return c.id;
+        if fabric.view(c.rect).iter().all(|&x| x == 1) {
+            return Some(c.id);
+        }
+    }
+    None
+}
+
+pub struct Day3;
+
+impl Solution for Day3 {
+    const DAY: u32 = 3;
+    const TITLE: &'static str = "No Matter How You Slice It";
+    const DEFAULT_INPUT: &'static str = DEFAULT_INPUT;
+
+    type Parsed = Vec<Claim>;
+    type Answer1 = usize;
+    type Answer2 = u32;
+
+    fn parse(input: &Input) -> Result<Vec<Claim>> {
+        read_claims(input)
+    }
+
+    fn part1(claims: &Vec<Claim>) -> Result<usize> {
+        let fabric = layout_claims(claims)?;
+        Ok(find_overallocated_cells(&fabric))
+    }
+
+    fn part2(claims: &Vec<Claim>) -> Result<u32> {
+        let fabric = layout_claims(claims)?;
+        Ok(find_nonoverlappig_rect(claims, &fabric).ok_or("Every claim overlaps another one")?)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use proptest::prelude::*;
+
+    const EXAMPLE: &str = "#1 @ 1,3: 4x4\n#2 @ 3,1: 4x4\n#3 @ 5,5: 2x2\n";
+
+    #[test]
+    fn example_part1() {
+        let claims = Day3::parse(&Input::text("example", EXAMPLE)).unwrap();
+        assert_eq!(Day3::part1(&claims).unwrap(), 4);
+    }
+
+    #[test]
+    fn example_part2() {
+        let claims = Day3::parse(&Input::text("example", EXAMPLE)).unwrap();
+        assert_eq!(Day3::part2(&claims).unwrap(), 3);
+    }
+
+    #[test]
+    fn render_example() {
+        use aoc_common::{Render, Rgb};
+        let claims = Day3::parse(&Input::text("example", EXAMPLE)).unwrap();
+        let image = Day3::render(&claims, 2).unwrap();
+        assert_eq!((image.width(), image.height()), (7, 7));
+        assert_eq!(image.get(0, 0), Rgb::WHITE);
+        assert_eq!(image.get(3, 3), aoc_common::render::heat(1.0));
+        assert_ne!(image.get(1, 3), image.get(3, 3));
+        assert_eq!(image.get(5, 5), render::INTACT);
+        assert_eq!(image.legend.len(), 3);
+        assert_eq!(Day3::render(&claims, 1).unwrap().legend.len(), 2);
+    }
+
+    #[test]
+    fn malformed_claims() {
+        assert!(Day3::part1(&vec![]).is_err());
+        let huge = "#1 @ 4294967295,4294967295: 4294967295x4294967295\n";
+        let claims = Day3::parse(&Input::text("huge", huge)).unwrap();
+        assert!(Day3::part1(&claims).is_err());
+        // a small fabric, claimed over and over
+        let piled = "#1 @ 0,0: 1000x1000\n".repeat(300);
+        let claims = Day3::parse(&Input::text("piled", piled)).unwrap();
+        let err = Day3::part1(&claims).unwrap_err();
+        assert!(err.to_string().contains("too much work"), "{}", err);
+        let overlapping =
+            Day3::parse(&Input::text("overlap", "#1 @ 0,0: 2x2\n#2 @ 1,1: 2x2")).unwrap();
+        assert!(Day3::part2(&overlapping).is_err());
+    }
+
+    #[test]
+    fn claims_round_trip() {
+        let line = "#123 @ 4,56: 7x8";
+        let claim = Claim::from_line(line).unwrap();
+        assert_eq!(claim.rect, Rect::new(4, 56, 7, 8));
+        assert_eq!(claim.to_string(), line);
+        assert_eq!(Claim::from_line(" #123@4, 56 : 7 x 8 "), Ok(claim));
+        let err = Claim::from_line("#1 @ 3,-2: 5x4").unwrap_err();
+        assert_eq!(err.location().columns, 7..9);
+    }
+
+    #[test]
+    fn generated_tiny() {
+        for size in 0..2 {
+            let input = aoc_common::gen::generate::<Day3>(7, size, &Default::default());
+            aoc_common::gen::check::<Day3>(&input).unwrap();
+        }
+    }
+
+    proptest! {
+        #[test]
+        fn generated_claims_round_trip(seed: u64, size in 1usize..50) {
+            let input = aoc_common::gen::generate::<Day3>(seed, size, &Default::default());
+            for line in aoc_common::read_lines(&input).unwrap() {
+                prop_assert_eq!(Claim::from_line(&line).unwrap().to_string(), line);
+            }
+        }
+
+        #[test]
+        fn generated_matches_oracle(seed: u64, size in 1usize..100, density in 0.1f64..2.0) {
+            let params = gen::Params { density, ..Default::default() };
+            let input = aoc_common::gen::generate::<Day3>(seed, size, &params);
+            let checked = aoc_common::gen::check::<Day3>(&input);
+            prop_assert!(checked.is_ok(), "{}", checked.unwrap_err());
         }
     }
-    0
 }
@@ -0,0 +1,39 @@
+//! Heat map of how many claims cover each square inch of the fabric.
+use aoc_common::render::heat;
+use aoc_common::{Image, Render, Result, Rgb};
+
+use crate::{find_nonoverlappig_rect, layout_claims, Claim, Day3};
+
+/// Colour of the claim that overlaps no other one, in part 2.
+pub(crate) const INTACT: Rgb = Rgb(40, 110, 220);
+
+/// Colour of squares covered by `count` out of at most `most` claims.
+fn shade(count: i32, most: i32) -> Rgb {
+    // one claim is faint but visible, the most claims are darkest
+    heat(0.15 + 0.85 * f64::from(count - 1) / f64::from((most - 1).max(1)))
+}
+
+impl Render for Day3 {
+    fn render(claims: &Vec<Claim>, part: u32) -> Result<Image> {
+        let fabric = layout_claims(claims)?;
+        let most = fabric.values().copied().max().unwrap_or(0).max(1);
+        let mut image = Image::new(fabric.width(), fabric.height(), Rgb::WHITE);
+        image.title = "Day 3: claims per square inch".to_string();
+        for (p, &count) in fabric.iter() {
+            if count > 0 {
+                image.set(p.x as usize, p.y as usize, shade(count, most));
+            }
+        }
+        image.add_legend(shade(1, most), "1 claim");
+        image.add_legend(shade(most, most), format!("{} claims", most));
+        if part == 2 {
+            if let Some(id) = find_nonoverlappig_rect(claims, &fabric) {
+                let c = claims.iter().find(|c| c.id == id).unwrap();
+                let r = c.rect;
+                image.fill(r.x as usize, r.y as usize, r.width, r.height, INTACT);
+                image.add_legend(INTACT, format!("#{}, overlaps no other claim", id));
+            }
+        }
+        Ok(image)
+    }
+}
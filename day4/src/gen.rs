@@ -0,0 +1,175 @@
+//! Shuffled guard logs for a number of guards, with one guard planted as both
+//! the sleepiest one and the one most often asleep on the same minute.
+use std::collections::HashMap;
+
+use chrono::{Duration, NaiveDate};
+
+use aoc_common::{Generator, Result, Rng};
+
+use crate::{Action, Day4, Note};
+
+pub struct Params {
+    /// Number of different guards.
+    pub guards: usize,
+    /// Most naps in one shift.
+    pub max_naps: usize,
+}
+
+impl Default for Params {
+    fn default() -> Params {
+        Params {
+            guards: 10,
+            max_naps: 3,
+        }
+    }
+}
+
+/// Naps start at or after this minute, so that they never come before the
+/// start of the shift.
+const FIRST_NAP_MINUTE: u32 = 6;
+
+struct Shift {
+    guard: u32,
+    naps: Vec<(u32, u32)>,
+}
+
+/// Minutes asleep per guard, and how often each guard was asleep per minute.
+type SleepStats = (HashMap<u32, u32>, HashMap<(u32, u32), u32>);
+
+fn add_nap(stats: &mut SleepStats, guard: u32, start: u32, end: u32) {
+    *stats.0.entry(guard).or_insert(0) += end - start;
+    for minute in start..end {
+        *stats.1.entry((guard, minute)).or_insert(0) += 1;
+    }
+}
+
+/// The single key with the largest value.
+fn unique_max<K: Copy>(values: impl Iterator<Item = (K, u32)>) -> Result<K> {
+    let mut best: Option<(K, u32)> = None;
+    let mut tied = false;
+    for (key, value) in values {
+        match best {
+            Some((_, max)) if value < max => {}
+            Some((_, max)) if value == max => tied = true,
+            _ => {
+                best = Some((key, value));
+                tied = false;
+            }
+        }
+    }
+    match best {
+        Some(_) if tied => Err("The answer is ambiguous".into()),
+        Some((key, _)) => Ok(key),
+        None => Err("No guard ever falls asleep".into()),
+    }
+}
+
+fn sleep_stats(notes: &[Note]) -> Result<SleepStats> {
+    let mut stats = SleepStats::default();
+    let mut guard = None;
+    let mut asleep = None;
+    for note in notes {
+        match (&note.action, guard, asleep) {
+            (Action::BeginShift(id), _, None) => guard = Some(*id),
+            (Action::FallAsleep, Some(_), None) => asleep = Some(note.minute),
+            (Action::WakeUp, Some(id), Some(start)) if start <= note.minute => {
+                add_nap(&mut stats, id, start, note.minute);
+                asleep = None;
+            }
+            _ => return Err("The log is inconsistent".into()),
+        }
+    }
+    Ok(stats)
+}
+
+impl Generator for Day4 {
+    type Params = Params;
+
+    fn generate(rng: &mut Rng, size: usize, params: &Params) -> String {
+        let mut guards: Vec<u32> = Vec::new();
+        let max_id = 4000.max(2 * params.guards as i64);
+        while guards.len() < params.guards.max(1) {
+            let id = rng.range(1, max_id) as u32;
+            if !guards.contains(&id) {
+                guards.push(id);
+            }
+        }
+
+        let mut stats = SleepStats::default();
+        let mut shifts = Vec::new();
+        for _ in 0..size {
+            let guard = *rng.pick(&guards);
+            let mut minutes: Vec<u32> = (FIRST_NAP_MINUTE..60).collect();
+            rng.shuffle(&mut minutes);
+            let max_naps = params.max_naps.min(minutes.len() / 2);
+            let naps = rng.below(max_naps as u64 + 1) as usize;
+            let mut minutes = minutes[..2 * naps].to_vec();
+            minutes.sort_unstable();
+            let naps: Vec<(u32, u32)> = minutes.chunks(2).map(|m| (m[0], m[1])).collect();
+            for &(start, end) in &naps {
+                add_nap(&mut stats, guard, start, end);
+            }
+            shifts.push(Shift { guard, naps });
+        }
+
+        // Make `planted` sleep longest, by adding shifts with long naps, and
+        // most often on `minute`, by adding shifts with a nap on just that.
+        let planted = *rng.pick(&guards);
+        let minute = rng.range(i64::from(FIRST_NAP_MINUTE), 58) as u32;
+        let total = |stats: &SleepStats, guard| stats.0.get(&guard).copied().unwrap_or(0);
+        let count = |stats: &SleepStats, guard, m| stats.1.get(&(guard, m)).copied().unwrap_or(0);
+        let others_total = guards.iter().filter(|&&g| g != planted);
+        let others_total = others_total.map(|&g| total(&stats, g)).max().unwrap_or(0);
+        while total(&stats, planted) <= others_total {
+            add_nap(&mut stats, planted, FIRST_NAP_MINUTE, 59);
+            let naps = vec![(FIRST_NAP_MINUTE, 59)];
+            shifts.push(Shift {
+                guard: planted,
+                naps,
+            });
+        }
+        let others_count = stats.1.iter().filter(|(&key, _)| key != (planted, minute));
+        let others_count = others_count.map(|(_, &n)| n).max().unwrap_or(0);
+        while count(&stats, planted, minute) <= others_count {
+            add_nap(&mut stats, planted, minute, minute + 1);
+            let naps = vec![(minute, minute + 1)];
+            shifts.push(Shift {
+                guard: planted,
+                naps,
+            });
+        }
+
+        rng.shuffle(&mut shifts);
+        let first_day = NaiveDate::from_ymd_opt(1518, 1, 1).unwrap();
+        let mut lines = Vec::new();
+        for (day, shift) in shifts.iter().enumerate() {
+            let date = first_day + Duration::days(day as i64);
+            let start = if rng.chance(0.5) {
+                format!("{} 23:{:02}", date.pred_opt().unwrap(), rng.range(40, 59))
+            } else {
+                format!("{} 00:{:02}", date, rng.below(u64::from(FIRST_NAP_MINUTE)))
+            };
+            lines.push(format!("[{}] Guard #{} begins shift", start, shift.guard));
+            for &(start, end) in &shift.naps {
+                lines.push(format!("[{} 00:{:02}] falls asleep", date, start));
+                lines.push(format!("[{} 00:{:02}] wakes up", date, end));
+            }
+        }
+        rng.shuffle(&mut lines);
+        lines.iter().map(|line| format!("{}\n", line)).collect()
+    }
+
+    fn oracle1(notes: &Vec<Note>) -> Result<u64> {
+        let (totals, counts) = sleep_stats(notes)?;
+        let guard = unique_max(totals.into_iter())?;
+        let minutes = counts.into_iter().filter(|&((g, _), _)| g == guard);
+        let minute = unique_max(minutes.map(|((_, m), n)| (m, n)))?;
+        Ok(u64::from(guard) * u64::from(minute))
+    }
+
+    fn oracle2(notes: &Vec<Note>) -> Result<u64> {
+        let (_, counts) = sleep_stats(notes)?;
+        let (guard, minute) = unique_max(counts.into_iter())?;
+        Ok(u64::from(guard) * u64::from(minute))
+    }
+}
@@ -1,19 +1,20 @@
 #![allow(dead_code)]
-#[macro_use]
-extern crate lazy_static;
 extern crate chrono;
-extern crate regex;
 
-use chrono::NaiveDateTime;
 use chrono::Timelike;
-use regex::Regex;
-use std::boxed::Box;
-use std::error;
-use std::fs::File;
-use std::io::{BufRead, BufReader};
-use std::path::Path;
+use chrono::{DateTime, NaiveDateTime};
+use itertools::Itertools;
+use std::cmp::Reverse;
+use std::collections::HashMap;
+use std::fmt;
 
-pub type Result<T> = ::std::result::Result<T, Box<dyn error::Error>>;
+use aoc_common::parse::fields;
+use aoc_common::{FromLine, Input, ParseError, Result, Solution};
+
+pub mod gen;
+pub mod render;
+
+pub const DEFAULT_INPUT: &str = concat!(env!("CARGO_MANIFEST_DIR"), "/data/input.txt");
 
 #[derive(Debug, PartialEq)]
 pub enum Action {
@@ -30,59 +31,339 @@ pub struct Note {
 
 impl Action {
     pub fn is_begin_shift(&self) -> bool {
+        matches!(self, Action::BeginShift(_))
+    }
+}
+
+/// Format of the timestamps, both for reading and writing them.
+const TIMESTAMP_FORMAT: &str = "%Y-%m-%d %H:%M";
+
+impl FromLine for Action {
+    fn from_line(line: &str) -> std::result::Result<Action, ParseError> {
+        if let Ok(mut guard) = fields("Guard #{} begins shift", line) {
+            return Ok(Action::BeginShift(guard.number()?));
+        } else if fields("wakes up", line).is_ok() {
+            return Ok(Action::WakeUp);
+        } else if fields("falls asleep", line).is_ok() {
+            return Ok(Action::FallAsleep);
+        }
+        Err(ParseError::syntax(
+            0..line.len(),
+            "'Guard #N begins shift', 'falls asleep' or 'wakes up'",
+        ))
+    }
+}
+
+impl fmt::Display for Action {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
         match self {
-            Action::BeginShift(_) => true,
-            _ => false,
+            Action::BeginShift(id) => write!(f, "Guard #{} begins shift", id),
+            Action::FallAsleep => write!(f, "falls asleep"),
+            Action::WakeUp => write!(f, "wakes up"),
         }
     }
 }
 
-fn parse_action(line: &str) -> Result<Action> {
-    lazy_static! {
-        static ref RE: Regex = Regex::new(r"Guard #(\S+) begins.*").unwrap();
+impl FromLine for Note {
+    fn from_line(line: &str) -> std::result::Result<Note, ParseError> {
+        let mut fields = fields("[{}] {}", line)?;
+        let date_time =
+            fields.with(|date| NaiveDateTime::parse_from_str(date, TIMESTAMP_FORMAT))?;
+        Ok(Note {
+            timestamp: date_time.and_utc().timestamp(),
+            minute: date_time.minute(),
+            action: fields.record()?,
+        })
+    }
+}
+
+impl fmt::Display for Note {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let date_time = DateTime::from_timestamp(self.timestamp, 0).ok_or(fmt::Error)?;
+        let date_time = date_time.format(TIMESTAMP_FORMAT);
+        write!(f, "[{}] {}", date_time, self.action)
+    }
+}
+
+pub fn read_notes(input: &Input) -> Result<Vec<Note>> {
+    let mut notes: Vec<Note> = aoc_common::read_records(input)?;
+    notes.sort_by_key(|note| note.timestamp);
+
+    Ok(notes)
+}
+
+pub type MinuteHistogram = [u32; 60];
+
+//struct MinuteHistogram
+/// Minutes asleep, the start inclusive and the end exclusive.
+pub type MinuteRange = (usize, usize);
+
+/// One guard's shift and when they slept during it.
+#[derive(Debug)]
+pub struct Shift {
+    pub guard: u32,
+    pub naps: Vec<MinuteRange>,
+}
+
+//impl Default for MinuteRange {
+//fn default() -> [0; 60]
+//}
+
+fn analyse_shift<'a>(id: u32, it: impl Iterator<Item = &'a Note>) -> Result<Vec<MinuteRange>> {
+    //let mut min_hist: MinuteHistogram = [0; 60];
+    let mut ranges: Vec<MinuteRange> = Vec::new();
+    let v: Vec<&'a Note> = it.take_while(|n| !n.action.is_begin_shift()).collect();
+
+    if !v.len().is_multiple_of(2) {
+        return Err(format!("Guard #{} falls asleep without waking up", id).into());
     }
-    if let Some(caps) = RE.captures(line) {
-        let id: u32 = caps.get(1).unwrap().as_str().parse()?;
-        return Ok(Action::BeginShift(id));
-    } else if line == "wakes up" {
-        return Ok(Action::WakeUp);
-    } else if line == "falls asleep" {
-        return Ok(Action::FallAsleep);
+    for (start, end) in v.iter().tuples() {
+        if start.action != Action::FallAsleep || end.action != Action::WakeUp {
+            return Err(format!("Guard #{} wakes up without falling asleep", id).into());
+        }
+        if start.minute > end.minute {
+            return Err(format!("Guard #{} sleeps past the midnight hour", id).into());
+        }
+        //println!("{:?} - {:?}", start.minute, end.minute);
+        ranges.push((start.minute as usize, end.minute as usize));
     }
-    Err("Invalid action".into())
+    Ok(ranges)
 }
 
-fn parse_note(line: &str) -> Result<Note> {
-    lazy_static! {
-        static ref RE: Regex = Regex::new(r"\[(.+)\] (.+)").unwrap();
+/// The shifts in the notes, in order.
+pub fn shifts(notes: &[Note]) -> Result<Vec<Shift>> {
+    let mut shifts = Vec::new();
+    let mut it = notes.iter();
+    while let Some(note) = it.next() {
+        if let Action::BeginShift(id) = note.action {
+            let naps = analyse_shift(id, it.clone())?;
+            shifts.push(Shift { guard: id, naps });
+        }
     }
-    let caps = RE.captures(line).ok_or("Parse error")?;
-    let date = &caps[1];
-    let action = &caps[2];
-    let date_time = NaiveDateTime::parse_from_str(date, "%Y-%m-%d %H:%M")?;
-    let action = parse_action(action)?;
-    Ok(Note {
-        timestamp: date_time.timestamp(),
-        minute: date_time.minute(),
-        action: action,
-    })
+    Ok(shifts)
 }
 
-pub fn read_notes(filename: &Path) -> Result<Vec<Note>> {
-    let file = File::open(filename).map_err(|e| {
-        format!("Unable to open input file '{}': {}", filename.display(), e).to_string()
-    })?;
-    let file = BufReader::new(file);
-    let mut notes = Vec::new();
-    for line in file.lines() {
-        let line = line?;
+pub fn make_minute_histograms(notes: &[Note]) -> Result<HashMap<u32, MinuteHistogram>> {
+    let mut minute_histograms: HashMap<u32, MinuteHistogram> = HashMap::new();
 
-        match parse_note(&line) {
-            Ok(n) => notes.push(n),
-            Err(e) => return Err(e.into()),
+    for shift in shifts(notes)? {
+        for (start, end) in shift.naps {
+            let entry = minute_histograms.entry(shift.guard).or_insert([0; 60]);
+            for m in &mut entry[start..end] {
+                *m += 1;
+            }
         }
     }
-    notes.sort_by_key(|note| note.timestamp);
+    Ok(minute_histograms)
+}
 
-    Ok(notes)
+fn find_max_index(mins: &MinuteHistogram) -> u32 {
+    let (index, _) = mins.iter().enumerate().max_by_key(|x| x.1).unwrap();
+    index as u32
+}
+
+struct HistogramItem {
+    index: u32,
+    value: u32,
+}
+
+fn find_max_item(mins: &MinuteHistogram) -> HistogramItem {
+    let index = find_max_index(mins);
+    HistogramItem {
+        index,
+        value: mins[index as usize],
+    }
+}
+
+/// Guard ID times minute for the guard asleep the most minutes. Ties go to
+/// the lowest guard ID, so the answer does not depend on map order.
+pub fn task_1(minute_histograms: &HashMap<u32, MinuteHistogram>) -> Option<u64> {
+    struct MinuteSum {
+        id: u32,
+        sum: u32,
+    }
+    let sums = minute_histograms.iter().map(|(id, minutes)| MinuteSum {
+        id: *id,
+        sum: minutes.iter().sum::<u32>(),
+    });
+
+    let sleepiest = sums.max_by_key(|x| (x.sum, Reverse(x.id)))?;
+    let minutes = &minute_histograms[&sleepiest.id];
+    let max_index = find_max_index(minutes);
+    Some(u64::from(max_index) * u64::from(sleepiest.id))
+}
+
+/// Guard ID times minute for the guard most often asleep on the same minute,
+/// with ties going to the lowest guard ID like in [`task_1`].
+pub fn task_2(minute_histograms: &HashMap<u32, MinuteHistogram>) -> Option<u64> {
+    let maxs = minute_histograms
+        .iter()
+        .map(|(id, minutes)| (id, find_max_item(minutes)));
+
+    let max_id = maxs.max_by_key(|x| ((x.1).value, Reverse(*x.0)))?;
+
+    let guard_id = max_id.0;
+    let max_minute = (max_id.1).index;
+    Some(u64::from(*guard_id) * u64::from(max_minute))
+}
+
+pub struct Day4;
+
+impl Solution for Day4 {
+    const DAY: u32 = 4;
+    const TITLE: &'static str = "Repose Record";
+    const DEFAULT_INPUT: &'static str = DEFAULT_INPUT;
+
+    type Parsed = Vec<Note>;
+    type Answer1 = u64;
+    type Answer2 = u64;
+
+    fn parse(input: &Input) -> Result<Vec<Note>> {
+        read_notes(input)
+    }
+
+    fn part1(notes: &Vec<Note>) -> Result<u64> {
+        Ok(task_1(&make_minute_histograms(notes)?).ok_or("No guard ever falls asleep")?)
+    }
+
+    fn part2(notes: &Vec<Note>) -> Result<u64> {
+        Ok(task_2(&make_minute_histograms(notes)?).ok_or("No guard ever falls asleep")?)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use proptest::prelude::*;
+
+    // The example notes, shuffled since the real input is not sorted either.
+    const EXAMPLE: &str = "\
+[1518-11-01 00:25] wakes up
+[1518-11-01 00:00] Guard #10 begins shift
+[1518-11-01 00:05] falls asleep
+[1518-11-01 00:30] falls asleep
+[1518-11-01 00:55] wakes up
+[1518-11-01 23:58] Guard #99 begins shift
+[1518-11-02 00:40] falls asleep
+[1518-11-02 00:50] wakes up
+[1518-11-03 00:05] Guard #10 begins shift
+[1518-11-03 00:24] falls asleep
+[1518-11-03 00:29] wakes up
+[1518-11-04 00:02] Guard #99 begins shift
+[1518-11-04 00:36] falls asleep
+[1518-11-04 00:46] wakes up
+[1518-11-05 00:03] Guard #99 begins shift
+[1518-11-05 00:45] falls asleep
+[1518-11-05 00:55] wakes up
+";
+
+    #[test]
+    fn example_part1() {
+        let notes = Day4::parse(&Input::text("example", EXAMPLE)).unwrap();
+        assert_eq!(Day4::part1(&notes).unwrap(), 240);
+    }
+
+    #[test]
+    fn example_part2() {
+        let notes = Day4::parse(&Input::text("example", EXAMPLE)).unwrap();
+        assert_eq!(Day4::part2(&notes).unwrap(), 4455);
+    }
+
+    #[test]
+    fn ties_go_to_lowest_id() {
+        // both guards sleep 10 minutes, twice on one minute
+        let log = "\
+[1518-11-01 00:00] Guard #99 begins shift
+[1518-11-01 00:10] falls asleep
+[1518-11-01 00:14] wakes up
+[1518-11-02 00:00] Guard #10 begins shift
+[1518-11-02 00:30] falls asleep
+[1518-11-02 00:34] wakes up
+[1518-11-03 00:00] Guard #99 begins shift
+[1518-11-03 00:13] falls asleep
+[1518-11-03 00:19] wakes up
+[1518-11-04 00:00] Guard #10 begins shift
+[1518-11-04 00:33] falls asleep
+[1518-11-04 00:39] wakes up
+";
+        let notes = Day4::parse(&Input::text("tie", log)).unwrap();
+        assert_eq!(Day4::part1(&notes).unwrap(), 10 * 33);
+        assert_eq!(Day4::part2(&notes).unwrap(), 10 * 33);
+    }
+
+    #[test]
+    fn render_example() {
+        use aoc_common::render::category;
+        use aoc_common::{Render, Rgb};
+        let notes = Day4::parse(&Input::text("example", EXAMPLE)).unwrap();
+        let image = Day4::render(&notes, 1).unwrap();
+        assert_eq!((image.width(), image.height()), (60, 5));
+        assert_eq!(image.get(4, 0), Rgb::GREY);
+        assert_eq!(image.get(5, 0), category(0));
+        assert_eq!(image.get(24, 0), category(0));
+        assert_eq!(image.get(25, 0), Rgb::GREY);
+        assert_eq!(image.get(40, 1), category(1));
+        let legend: Vec<&str> = image.legend.iter().map(|(_, l)| l.as_str()).collect();
+        assert_eq!(legend, ["#10: 50 min asleep", "#99: 30 min asleep"]);
+    }
+
+    #[test]
+    fn malformed_logs() {
+        let logs = [
+            "",
+            "[1518-11-01 00:00] Guard #10 begins shift\n",
+            "[1518-11-01 00:00] Guard #10 begins shift\n[1518-11-01 00:05] falls asleep\n",
+            "[1518-11-01 00:00] Guard #10 begins shift\n[1518-11-01 00:05] wakes up\n",
+            "[1518-11-01 00:00] Guard #10 begins shift\n[1518-11-01 00:50] falls asleep\n\
+             [1518-11-01 01:05] wakes up\n",
+        ];
+        for log in logs.iter() {
+            let notes = Day4::parse(&Input::text("malformed", *log)).unwrap();
+            assert!(Day4::part1(&notes).is_err(), "{}", log);
+            assert!(Day4::part2(&notes).is_err(), "{}", log);
+        }
+    }
+
+    #[test]
+    fn notes_round_trip() {
+        for line in EXAMPLE.lines() {
+            assert_eq!(Note::from_line(line).unwrap().to_string(), line);
+        }
+        let note = Note::from_line("[1518-11-01 23:58]  Guard #99  begins shift ").unwrap();
+        assert_eq!((note.minute, note.action), (58, Action::BeginShift(99)));
+
+        let line = "[1518-11-01 00:05] Guard #x begins shift";
+        let err = Note::from_line(line).unwrap_err();
+        assert!(matches!(err, ParseError::Number { .. }));
+        assert_eq!(&line[err.location().columns.clone()], "x");
+        let line = "[1518-02-30 00:05] wakes up";
+        let err = Note::from_line(line).unwrap_err();
+        assert_eq!(&line[err.location().columns.clone()], "1518-02-30 00:05");
+    }
+
+    #[test]
+    fn generated_tiny() {
+        for size in 0..2 {
+            let input = aoc_common::gen::generate::<Day4>(7, size, &Default::default());
+            aoc_common::gen::check::<Day4>(&input).unwrap();
+        }
+    }
+
+    proptest! {
+        #[test]
+        fn generated_notes_round_trip(seed: u64, size in 0usize..50) {
+            let input = aoc_common::gen::generate::<Day4>(seed, size, &Default::default());
+            for line in aoc_common::read_lines(&input).unwrap() {
+                prop_assert_eq!(Note::from_line(&line).unwrap().to_string(), line);
+            }
+        }
+
+        #[test]
+        fn generated_matches_oracle(seed: u64, size in 0usize..100, guards in 1usize..20) {
+            let params = gen::Params { guards, ..Default::default() };
+            let input = aoc_common::gen::generate::<Day4>(seed, size, &params);
+            let checked = aoc_common::gen::check::<Day4>(&input);
+            prop_assert!(checked.is_ok(), "{}", checked.unwrap_err());
+        }
+    }
 }
@@ -0,0 +1,44 @@
+//! Timelines of the shifts: a row per shift and a column per minute of the
+//! midnight hour, coloured by guard where they were asleep.
+use std::collections::HashMap;
+
+use aoc_common::render::category;
+use aoc_common::{Image, Render, Result, Rgb};
+
+use crate::{shifts, Day4, Note};
+
+impl Render for Day4 {
+    fn render(notes: &Vec<Note>, _part: u32) -> Result<Image> {
+        let shifts = shifts(notes)?;
+        let mut asleep: HashMap<u32, usize> = HashMap::new();
+        for shift in &shifts {
+            let minutes = shift
+                .naps
+                .iter()
+                .map(|(start, end)| end - start)
+                .sum::<usize>();
+            *asleep.entry(shift.guard).or_insert(0) += minutes;
+        }
+        // colours in order of the ids, whatever order the shifts come in
+        let mut guards: Vec<u32> = asleep.keys().copied().collect();
+        guards.sort_unstable();
+        let color = |guard: u32| category(guards.binary_search(&guard).unwrap());
+
+        let mut image = Image::new(60, shifts.len(), Rgb::GREY);
+        image.scale = 6;
+        image.title = "Day 4: naps per shift, 00:00 to 00:59".to_string();
+        for (y, shift) in shifts.iter().enumerate() {
+            for &(start, end) in &shift.naps {
+                image.fill(start, y, end - start, 1, color(shift.guard));
+            }
+        }
+        // legend with the sleepiest guard first
+        let mut by_sleep = guards.clone();
+        by_sleep.sort_by_key(|guard| (std::cmp::Reverse(asleep[guard]), *guard));
+        for guard in by_sleep {
+            let label = format!("#{}: {} min asleep", guard, asleep[&guard]);
+            image.add_legend(color(guard), label);
+        }
+        Ok(image)
+    }
+}
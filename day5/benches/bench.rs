@@ -0,0 +1,49 @@
+#[macro_use]
+extern crate bencher;
+
+use aoc_common::{bench, Input};
+use bencher::{Bencher, TestDescAndFn};
+use day5::Day5;
+
+fn real() -> Input {
+    bench::load(day5::DEFAULT_INPUT)
+}
+
+// Generated input with a polymer of 200000 units.
+fn scaled() -> Input {
+    aoc_common::gen::generate::<Day5>(1, 200000, &Default::default())
+}
+
+fn parse(b: &mut Bencher) {
+    bench::parse::<Day5>(b, &real())
+}
+fn part1(b: &mut Bencher) {
+    bench::part1::<Day5>(b, &real())
+}
+fn part2(b: &mut Bencher) {
+    bench::part2::<Day5>(b, &real())
+}
+fn parse_scaled(b: &mut Bencher) {
+    bench::parse::<Day5>(b, &scaled())
+}
+fn part1_scaled(b: &mut Bencher) {
+    bench::part1::<Day5>(b, &scaled())
+}
+fn part2_scaled(b: &mut Bencher) {
+    bench::part2::<Day5>(b, &scaled())
+}
+
+fn variants() -> Vec<TestDescAndFn> {
+    bench::variants::<Day5>(&real())
+}
+
+benchmark_group!(
+    benches,
+    parse,
+    part1,
+    part2,
+    parse_scaled,
+    part1_scaled,
+    part2_scaled
+);
+benchmark_main!(benches, variants);
@@ -0,0 +1,69 @@
+//! Random polymers of a given length, with a tunable chance of a unit
+//! reacting with the one before it.
+use aoc_common::{Generator, Result, Rng};
+
+use crate::Day5;
+
+pub struct Params {
+    /// Number of different unit types, starting from `a`.
+    pub letters: u8,
+    /// Chance of a unit being the opposite polarity of the one before it.
+    pub reactivity: f64,
+}
+
+impl Default for Params {
+    fn default() -> Params {
+        Params {
+            letters: 26,
+            reactivity: 0.3,
+        }
+    }
+}
+
+fn reacts(a: u8, b: u8) -> bool {
+    a != b && a.eq_ignore_ascii_case(&b)
+}
+
+/// Remove the first reacting pair until there is none left.
+fn react_fully(polymer: &[u8]) -> usize {
+    let mut polymer = polymer.to_vec();
+    while let Some(i) = (1..polymer.len()).find(|&i| reacts(polymer[i - 1], polymer[i])) {
+        polymer.drain(i - 1..=i);
+    }
+    polymer.len()
+}
+
+impl Generator for Day5 {
+    type Params = Params;
+
+    fn generate(rng: &mut Rng, size: usize, params: &Params) -> String {
+        let letters = u64::from(params.letters.clamp(1, 26));
+        let mut polymer: Vec<u8> = Vec::with_capacity(size + 1);
+        for _ in 0..size {
+            let unit = match polymer.last() {
+                Some(&last) if rng.chance(params.reactivity) => last ^ 0x20,
+                _ if rng.chance(0.5) => b'a' + rng.below(letters) as u8,
+                _ => b'A' + rng.below(letters) as u8,
+            };
+            polymer.push(unit);
+        }
+        polymer.push(b'\n');
+        String::from_utf8(polymer).unwrap()
+    }
+
+    fn oracle1(polymer: &Vec<u8>) -> Result<usize> {
+        Ok(react_fully(polymer))
+    }
+
+    fn oracle2(polymer: &Vec<u8>) -> Result<usize> {
+        let shortest = (b'a'..=b'z').map(|unit| {
+            let removed: Vec<u8> = polymer
+                .iter()
+                .filter(|u| !u.eq_ignore_ascii_case(&unit))
+                .copied()
+                .collect();
+            react_fully(&removed)
+        });
+        Ok(shortest.min().unwrap())
+    }
+}
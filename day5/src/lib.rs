@@ -0,0 +1,279 @@
+use aoc_common::{par, Input, ParseError, Result, Solution, Variant};
+
+pub mod gen;
+
+pub const DEFAULT_INPUT: &str = concat!(env!("CARGO_MANIFEST_DIR"), "/data/input.txt");
+
+pub fn read_polymer(input: &Input) -> Result<Vec<u8>> {
+    let lines = aoc_common::read_lines(input)?;
+    if let Some(extra) = lines.get(1) {
+        let err = ParseError::syntax(0..extra.len(), "the polymer on a single line");
+        return Err(err.at_line(&input.to_string(), 2, extra).into());
+    }
+    let line = lines.first().map_or("", String::as_str);
+    if let Some(pos) = line.find(|ch: char| !ch.is_ascii_alphabetic()) {
+        let err = ParseError::value(pos..pos + 1, "polymer units must be letters");
+        return Err(err.at_line(&input.to_string(), 1, line).into());
+    }
+    Ok(line.as_bytes().to_vec())
+}
+
+// O(n^2) version
+fn shrink_u8_1(input: &[u8]) -> Vec<u8> {
+    let mut old = input.to_vec();
+    let mut new: Vec<u8> = Vec::new();
+    loop {
+        let mut i = 0;
+        while i < old.len() {
+            if i < old.len() - 1 && (old[i] as i32 - old[i + 1] as i32).abs() == 32 {
+                i += 1;
+            } else {
+                new.push(old[i]);
+            }
+            i += 1;
+        }
+        if new.len() == old.len() {
+            break;
+        }
+        old = new.clone();
+        new.clear();
+    }
+    new
+}
+
+// O(n) version
+fn shrink_u8_2(input: &[u8]) -> Vec<u8> {
+    if input.len() <= 1 {
+        return input.to_vec();
+    }
+    let mut new: Vec<u8> = Vec::new();
+    for &next in input {
+        if new.is_empty() {
+            new.push(next);
+        } else {
+            let is_match = (*new.last().unwrap() as i32 - next as i32).abs() == 32;
+
+            if is_match {
+                new.pop().unwrap();
+            } else {
+                new.push(next);
+            }
+        }
+    }
+    new
+}
+
+fn u8_lowercase(ch: u8) -> u8 {
+    if ch.is_ascii_uppercase() {
+        return ch + 32;
+    }
+    ch
+}
+
+pub fn task1(input: &[u8]) -> usize {
+    shrink_u8_2(input).len()
+}
+
+/// Length of the fully reacted polymer after removing each unit type, by
+/// the lowercase unit removed.
+pub fn lengths_by_unit_removed(input: &[u8]) -> Vec<(u8, usize)> {
+    let units: Vec<u8> = (b'a'..=b'z').collect();
+    par::map(&units, |&unit_to_remove| {
+        let cleaned: Vec<u8> = input
+            .iter()
+            .filter(|&&ch| u8_lowercase(ch) != unit_to_remove)
+            .cloned()
+            .collect();
+
+        (unit_to_remove, shrink_u8_2(&cleaned).len())
+    })
+}
+
+pub fn task2(input: &[u8]) -> usize {
+    let lengths_by_unit_removed = lengths_by_unit_removed(input);
+    for &(unit, l) in &lengths_by_unit_removed {
+        log::debug!("without {}: {}", unit as char, l);
+    }
+    let min = lengths_by_unit_removed.iter().min_by_key(|x| x.1).unwrap();
+    min.1
+}
+
+pub struct Day5;
+
+impl Solution for Day5 {
+    const DAY: u32 = 5;
+    const TITLE: &'static str = "Alchemical Reduction";
+    const DEFAULT_INPUT: &'static str = DEFAULT_INPUT;
+
+    type Parsed = Vec<u8>;
+    type Answer1 = usize;
+    type Answer2 = usize;
+
+    fn parse(input: &Input) -> Result<Vec<u8>> {
+        read_polymer(input)
+    }
+
+    fn part1(polymer: &Vec<u8>) -> Result<usize> {
+        Ok(task1(polymer))
+    }
+
+    fn part2(polymer: &Vec<u8>) -> Result<usize> {
+        Ok(task2(polymer))
+    }
+
+    const VARIANTS: &'static [Variant<Vec<u8>>] = &[
+        Variant {
+            name: "shrink1",
+            part: 1,
+            solve: |_, polymer| Ok(shrink_u8_1(polymer).len().to_string()),
+        },
+        Variant {
+            name: "shrink2",
+            part: 1,
+            solve: |_, polymer| Ok(shrink_u8_2(polymer).len().to_string()),
+        },
+    ];
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{shrink_u8_1, shrink_u8_2, Day5};
+    use aoc_common::{par, Input, Solution};
+    use proptest::prelude::*;
+
+    const EXAMPLE: &str = "dabAcCaCBAcCcaDA\n";
+
+    #[test]
+    fn example_part1() {
+        let polymer = Day5::parse(&Input::text("example", EXAMPLE)).unwrap();
+        assert_eq!(Day5::part1(&polymer).unwrap(), 10);
+        test("dabAcCaCBAcCcaDA", "dabCBAcaDA");
+    }
+
+    #[test]
+    fn example_part2() {
+        let polymer = Day5::parse(&Input::text("example", EXAMPLE)).unwrap();
+        assert_eq!(Day5::part2(&polymer).unwrap(), 4);
+        let lengths = super::lengths_by_unit_removed(&polymer);
+        assert_eq!(&lengths[..4], &[(b'a', 6), (b'b', 8), (b'c', 4), (b'd', 6)]);
+    }
+
+    #[test]
+    fn line_endings() {
+        let crlf = Input::text("crlf", "\u{feff}dabAcCaCBAcCcaDA \r\n\r\n");
+        assert_eq!(Day5::parse(&crlf).unwrap(), EXAMPLE.trim_end().as_bytes());
+        assert_eq!(Day5::parse(&Input::text("empty", "")).unwrap(), b"");
+        assert!(Day5::parse(&Input::text("two lines", "dabA\ncCaC\n")).is_err());
+        assert!(Day5::parse(&Input::text("digits", "dab1\n")).is_err());
+    }
+
+    #[test]
+    fn variants_agree() {
+        aoc_common::cross_check::<Day5>(&Input::text("example", EXAMPLE)).unwrap();
+    }
+
+    #[test]
+    fn parallel_matches_sequential() {
+        let input = aoc_common::gen::generate::<Day5>(7, 20000, &Default::default());
+        let polymer = Day5::parse(&input).unwrap();
+        let lengths = super::lengths_by_unit_removed(&polymer);
+        let sequential = par::sequential(|| super::lengths_by_unit_removed(&polymer));
+        assert_eq!(lengths, sequential);
+    }
+
+    #[test]
+    fn generated_tiny() {
+        for size in 0..2 {
+            let input = aoc_common::gen::generate::<Day5>(7, size, &Default::default());
+            aoc_common::gen::check::<Day5>(&input).unwrap();
+        }
+    }
+
+    proptest! {
+        #[test]
+        fn shrink_variants_agree(polymer in "[aAbBcC]{0,200}") {
+            prop_assert_eq!(shrink_u8_1(polymer.as_bytes()), shrink_u8_2(polymer.as_bytes()));
+        }
+
+        #[test]
+        fn generated_matches_oracle(seed: u64, size in 0usize..200, reactivity in 0.0f64..1.0) {
+            let params = super::gen::Params { letters: 4, reactivity };
+            let input = aoc_common::gen::generate::<Day5>(seed, size, &params);
+            let checked = aoc_common::gen::check::<Day5>(&input);
+            prop_assert!(checked.is_ok(), "{}", checked.unwrap_err());
+        }
+
+        #[test]
+        fn variants_agree_on_generated(polymer in "[a-dA-D]{1,200}") {
+            let checked = aoc_common::cross_check::<Day5>(&Input::text("generated", polymer));
+            prop_assert!(checked.is_ok(), "{}", checked.unwrap_err());
+        }
+    }
+
+    fn test(input: &str, expected: &str) {
+        let r = shrink_u8_1(input.as_bytes());
+        assert_eq!(r, expected.as_bytes());
+        let r = shrink_u8_2(input.as_bytes());
+        assert_eq!(r, expected.as_bytes());
+    }
+
+    #[test]
+    fn empty_input() {
+        test("", "");
+    }
+
+    #[test]
+    fn single_char_input() {
+        test("A", "A");
+    }
+
+    #[test]
+    fn no_reduction() {
+        test("Ab", "Ab");
+    }
+
+    #[test]
+    fn reduction_0() {
+        test("Aa", "");
+    }
+    #[test]
+    fn reduction_1() {
+        test("aAa", "a");
+    }
+    #[test]
+    fn reduction_2() {
+        test("zAa", "z");
+    }
+    #[test]
+    fn reduction_3() {
+        test("ABba", "");
+    }
+    #[test]
+    fn reduction_4() {
+        test("zAak", "zk");
+    }
+    #[test]
+    fn reduction_5() {
+        test("ABbak", "k");
+    }
+    #[test]
+    fn reduction_6() {
+        test("zABba", "z");
+    }
+    #[test]
+    fn reduction_7() {
+        test("zABbak", "zk");
+    }
+    #[test]
+    fn reduction_8() {
+        test("zABCcbak", "zk");
+    }
+    #[test]
+    fn reduction_9() {
+        test("bBtrlaALR", "t");
+    }
+    #[test]
+    fn reduction_10() {
+        test("EekK", "");
+    }
+}
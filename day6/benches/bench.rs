@@ -0,0 +1,45 @@
+#[macro_use]
+extern crate bencher;
+
+use aoc_common::{bench, Input};
+use bencher::Bencher;
+use day6::Day6;
+
+fn real() -> Input {
+    bench::load(day6::DEFAULT_INPUT)
+}
+
+// Generated input with 100 coordinates.
+fn scaled() -> Input {
+    aoc_common::gen::generate::<Day6>(1, 100, &Default::default())
+}
+
+fn parse(b: &mut Bencher) {
+    bench::parse::<Day6>(b, &real())
+}
+fn part1(b: &mut Bencher) {
+    bench::part1::<Day6>(b, &real())
+}
+fn part2(b: &mut Bencher) {
+    bench::part2::<Day6>(b, &real())
+}
+fn parse_scaled(b: &mut Bencher) {
+    bench::parse::<Day6>(b, &scaled())
+}
+fn part1_scaled(b: &mut Bencher) {
+    bench::part1::<Day6>(b, &scaled())
+}
+fn part2_scaled(b: &mut Bencher) {
+    bench::part2::<Day6>(b, &scaled())
+}
+
+benchmark_group!(
+    benches,
+    parse,
+    part1,
+    part2,
+    parse_scaled,
+    part1_scaled,
+    part2_scaled
+);
+benchmark_main!(benches);
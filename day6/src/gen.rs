@@ -0,0 +1,97 @@
+//! Clouds of distinct coordinates, with at least one finite area.
+use std::collections::HashMap;
+
+use aoc_common::{Generator, Point, Rect, Result, Rng};
+
+use crate::{safe_margin, Coordinate, Day6, MAX_TOTAL_DISTANCE};
+
+pub struct Params {
+    /// Width and height of the square the coordinates are spread over.
+    pub spread: isize,
+}
+
+impl Default for Params {
+    fn default() -> Params {
+        Params { spread: 300 }
+    }
+}
+
+/// The only coordinate closest to `cell`, if there is one.
+fn closest(cs: &[Coordinate], cell: Coordinate) -> Option<usize> {
+    let mut by_distance: Vec<(isize, usize)> = cs
+        .iter()
+        .enumerate()
+        .map(|(i, &c)| (c.manhattan(cell), i))
+        .collect();
+    by_distance.sort_unstable();
+    match by_distance[..] {
+        [(d0, i), (d1, _), ..] if d0 < d1 => Some(i),
+        [(_, i)] => Some(i),
+        _ => None,
+    }
+}
+
+/// Closest cell counts per coordinate over the bounding box of `cs` grown by
+/// `margin` on all sides.
+fn areas(cs: &[Coordinate], margin: isize) -> HashMap<usize, usize> {
+    let mut areas = HashMap::new();
+    for cell in around(cs, margin).points() {
+        if let Some(i) = closest(cs, cell) {
+            *areas.entry(i).or_insert(0) += 1;
+        }
+    }
+    areas
+}
+
+/// The bounding box of `cs` grown by `margin` on all sides.
+fn around(cs: &[Coordinate], margin: isize) -> Rect {
+    let bounds = Rect::bounding_box(cs.iter().copied()).unwrap();
+    bounds.grow(margin)
+}
+
+impl Generator for Day6 {
+    type Params = Params;
+
+    fn generate(rng: &mut Rng, size: usize, params: &Params) -> String {
+        let spread = params.spread.max(3);
+        // one coordinate in the middle of a diamond has a finite area
+        let center = Point::new(spread / 2, spread / 2);
+        let r = rng.range(1, spread as i64 / 2) as isize;
+        let mut cs: Vec<Coordinate> = vec![center];
+        for &d in &[(-r, 0), (r, 0), (0, -r), (0, r)] {
+            cs.push(center + Point::from(d));
+        }
+        let limit = size.max(cs.len()).min((spread * spread) as usize);
+        while cs.len() < limit {
+            let c = Point::new(
+                rng.below(spread as u64) as isize,
+                rng.below(spread as u64) as isize,
+            );
+            if !cs.contains(&c) {
+                cs.push(c);
+            }
+        }
+        rng.shuffle(&mut cs);
+        cs.iter().map(|c| format!("{}, {}\n", c.x, c.y)).collect()
+    }
+
+    fn oracle1(cs: &Vec<Coordinate>) -> Result<usize> {
+        // areas that grow with the box around the coordinates are infinite
+        let inner = areas(cs, 1);
+        let outer = areas(cs, 2);
+        let finite = inner.iter().filter(|(i, n)| outer.get(i) == Some(n));
+        Ok(*finite
+            .map(|(_, n)| n)
+            .max()
+            .ok_or("Every area is infinite")?)
+    }
+
+    fn oracle2(cs: &Vec<Coordinate>) -> Result<usize> {
+        let mut size = 0;
+        for cell in around(cs, safe_margin(MAX_TOTAL_DISTANCE, cs.len())).points() {
+            let sum: isize = cs.iter().map(|&c| c.manhattan(cell)).sum();
+            size += (sum < MAX_TOTAL_DISTANCE) as usize;
+        }
+        Ok(size)
+    }
+}
@@ -0,0 +1,309 @@
+use aoc_common::parse::fields;
+use aoc_common::{grid, Grid, Input, ParseError, Point, Rect, Result, Solution};
+
+pub const DEFAULT_INPUT: &str = concat!(env!("CARGO_MANIFEST_DIR"), "/data/input.txt");
+
+pub type Coordinate = Point;
+
+/// Part 2 looks for cells with a distance sum below this.
+const MAX_TOTAL_DISTANCE: isize = 10000;
+
+pub mod gen;
+pub mod render;
+
+fn parse_coordinate(line: &str) -> std::result::Result<Coordinate, ParseError> {
+    let mut fields = fields("{}, {}", line)?;
+    let x: i32 = fields.number()?;
+    let y: i32 = fields.number()?;
+    Ok(Point::new(x as isize, y as isize))
+}
+
+pub fn read_coordinates(input: &Input) -> Result<Vec<Coordinate>> {
+    aoc_common::parse_lines(input, parse_coordinate)
+}
+
+/// The bounding box of the coordinates, the cells on its edge included.
+pub fn grid_bounds(cs: &[Coordinate]) -> Result<Rect> {
+    let bounds = Rect::bounding_box(cs.iter().copied()).ok_or("There are no coordinates")?;
+    grid::check_size(bounds, "grid")?;
+    Ok(bounds)
+}
+
+/// How far outside the bounding box of `n` coordinates a cell can be and
+/// still have a distance sum below `max`. Further out every coordinate is
+/// more than `max / n` away.
+pub fn safe_margin(max: isize, n: usize) -> isize {
+    max / n.max(1) as isize + 1
+}
+
+fn find_closest_coord(cs: &[Coordinate], coord: Coordinate) -> Option<usize> {
+    let distances = cs.iter().map(|&c| c.manhattan(coord)).collect::<Vec<_>>();
+
+    let (min_index, min_dist) = distances.iter().enumerate().min_by_key(|x| x.1).unwrap();
+
+    if distances.iter().filter(|&d| d == min_dist).count() > 1 {
+        return None;
+    }
+    Some(min_index)
+}
+
+type GridFunc<T> = dyn Fn(&[Coordinate], Coordinate) -> T + Sync;
+
+// the rows are independent, so they are spread over all cores with the
+// `parallel` feature
+fn calc_grid<T>(cs: &[Coordinate], bounds: Rect, func: &GridFunc<T>) -> Grid<T>
+where
+    T: Default + Send,
+{
+    Grid::par_from_fn(bounds, |cell| func(cs, cell))
+}
+
+pub fn task_1(cs: &[Coordinate]) -> Result<usize> {
+    let bounds = grid_bounds(cs)?;
+    // every cell measures the distance to every coordinate
+    grid::check_work(bounds.area().saturating_mul(cs.len()), "grid")?;
+    log::debug!("grid size: {}x{}", bounds.width, bounds.height);
+
+    let grid = calc_grid(cs, bounds, &find_closest_coord);
+    let largest = area_sizes(cs.len(), &grid).into_iter().flatten().max();
+    Ok(largest.ok_or("Every area is infinite")?)
+}
+
+/// Cells closest to each of the `n` coordinates, given the closest one per
+/// cell. `None` for the areas that reach the edge and so go on forever.
+fn area_sizes(n: usize, grid: &Grid<Option<usize>>) -> Vec<Option<usize>> {
+    let mut sizes = vec![Some(0); n];
+    for (cell, closest) in grid.iter() {
+        if let Some(c) = *closest {
+            if grid.on_edge(cell) {
+                // On grid edge => infinite area
+                sizes[c] = None;
+            }
+            if let Some(size) = &mut sizes[c] {
+                *size += 1;
+            }
+        }
+    }
+    sizes
+}
+
+pub fn task_2(cs: &[Coordinate]) -> Result<usize> {
+    if cs.is_empty() {
+        return Err("There are no coordinates".into());
+    }
+    // each axis sums the distances to every coordinate at up to this many
+    // positions
+    let positions = 2 * MAX_TOTAL_DISTANCE as usize + 1;
+    grid::check_work(2 * positions.saturating_mul(cs.len()), "safe region")?;
+    Ok(safe_region_size(cs, MAX_TOTAL_DISTANCE))
+}
+
+/// Distance sums from every position on one axis to `values`, for the
+/// positions where the sum is below `threshold`.
+fn axis_distance_sums(values: &[isize], threshold: isize) -> Vec<isize> {
+    let lo = values.iter().copied().min().unwrap_or(0);
+    let hi = values.iter().copied().max().unwrap_or(0);
+    // further out than this, the distance to `lo` or `hi` alone is too much
+    (hi - threshold..=lo + threshold)
+        .map(|p| values.iter().map(|&v| (p - v).abs()).sum())
+        .filter(|&sum| sum < threshold)
+        .collect()
+}
+
+/// Size of the region where the distance sum to all coordinates is below
+/// `threshold`.
+///
+/// The manhattan distance sum of a cell is the sum along x plus the sum along
+/// y, so rather than visiting every cell of the region, count the pairs of
+/// per-axis sums that add up to less than `threshold`.
+fn safe_region_size(cs: &[Coordinate], threshold: isize) -> usize {
+    let xs: Vec<isize> = cs.iter().map(|c| c.x).collect();
+    let ys: Vec<isize> = cs.iter().map(|c| c.y).collect();
+    let x_sums = axis_distance_sums(&xs, threshold);
+    let mut y_sums = axis_distance_sums(&ys, threshold);
+    y_sums.sort_unstable();
+
+    x_sums
+        .iter()
+        .map(|x_sum| y_sums.partition_point(|y_sum| x_sum + y_sum < threshold))
+        .sum()
+}
+
+pub struct Day6;
+
+impl Solution for Day6 {
+    const DAY: u32 = 6;
+    const TITLE: &'static str = "Chronal Coordinates";
+    const DEFAULT_INPUT: &'static str = DEFAULT_INPUT;
+
+    type Parsed = Vec<Coordinate>;
+    type Answer1 = usize;
+    type Answer2 = usize;
+
+    fn parse(input: &Input) -> Result<Vec<Coordinate>> {
+        read_coordinates(input)
+    }
+
+    fn part1(coords: &Vec<Coordinate>) -> Result<usize> {
+        task_1(coords)
+    }
+
+    fn part2(coords: &Vec<Coordinate>) -> Result<usize> {
+        task_2(coords)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use aoc_common::par;
+    use proptest::prelude::*;
+
+    const EXAMPLE: &str = "1, 1\n1, 6\n8, 3\n3, 4\n5, 5\n8, 9\n";
+
+    #[test]
+    fn example_part1() {
+        let coords = Day6::parse(&Input::text("example", EXAMPLE)).unwrap();
+        assert_eq!(Day6::part1(&coords).unwrap(), 17);
+    }
+
+    #[test]
+    fn bounds_include_edges() {
+        let corners = [(0, 0), (4, 0), (0, 4), (4, 4), (2, 2)];
+        let cs: Vec<Coordinate> = corners.iter().map(|&c| Point::from(c)).collect();
+        let bounds = grid_bounds(&cs).unwrap();
+        assert_eq!(bounds, Rect::new(0, 0, 5, 5));
+        assert!(cs.iter().all(|&c| bounds.contains(c)));
+        assert!(bounds.on_edge(Point::new(4, 2)));
+        // the corners reach the edge, only the middle one's diamond is finite
+        assert_eq!(task_1(&cs).unwrap(), 5);
+    }
+
+    #[test]
+    fn safe_margin_is_enough() {
+        let cs = [Point::new(0, 0), Point::new(3, 1)];
+        let margin = safe_margin(10, cs.len());
+        let outside = Point::new(3 + margin, 1);
+        assert!(cs.iter().map(|&c| c.manhattan(outside)).sum::<isize>() >= 10);
+        assert_eq!(safe_margin(10, 0), 11);
+    }
+
+    #[test]
+    fn example_part2() {
+        // The example uses a smaller threshold than the real puzzle.
+        let coords = Day6::parse(&Input::text("example", EXAMPLE)).unwrap();
+        assert_eq!(safe_region_size(&coords, 32), 16);
+    }
+
+    #[test]
+    fn render_example() {
+        use aoc_common::{Render, Rgb};
+        let coords = Day6::parse(&Input::text("example", EXAMPLE)).unwrap();
+        let areas = Day6::render(&coords, 1).unwrap();
+        assert_eq!((areas.width(), areas.height()), (8, 9));
+        // (1, 1) is a coordinate, (5, 1) is as close to two of them, and
+        // (5, 4) and (4, 5) are closest to (5, 5)
+        assert_eq!(areas.get(0, 0), Rgb::BLACK);
+        assert_eq!(areas.get(4, 0), Rgb::WHITE);
+        assert_eq!(areas.get(4, 3), areas.get(3, 4));
+        assert_ne!(areas.get(4, 3), areas.get(4, 0));
+        assert!(areas.legend[0].1.contains("17 cells"));
+
+        let region = Day6::render(&coords, 2).unwrap();
+        assert!(region
+            .title
+            .contains(&format!("{} cells", Day6::part2(&coords).unwrap())));
+    }
+
+    #[test]
+    fn malformed_coordinates() {
+        assert!(Day6::part1(&vec![]).is_err());
+        assert!(Day6::part2(&vec![]).is_err());
+        assert!(Day6::part1(&vec![Point::ORIGIN]).is_err());
+        let far_apart = Day6::parse(&Input::text("far", "-2147483648, 0\n2147483647, 0\n"));
+        assert!(Day6::part1(&far_apart.unwrap()).is_err());
+        assert!(Day6::parse(&Input::text("too far", "1, 2147483648\n")).is_err());
+    }
+
+    #[test]
+    fn too_much_work() {
+        // a grid well within the size limit, but with 2000 coordinates
+        let cs: Vec<Coordinate> = (0..2000).map(|i| Point::new(i % 1000, i / 2)).collect();
+        let err = task_1(&cs).unwrap_err();
+        assert!(err.to_string().contains("too much work"), "{}", err);
+        let cs = vec![Point::ORIGIN; 20000];
+        assert!(task_2(&cs)
+            .unwrap_err()
+            .to_string()
+            .contains("too much work"));
+    }
+
+    #[test]
+    fn single_coordinate_region() {
+        // a diamond with radius 9999 around the coordinate
+        let coords = vec![Point::new(3, 4)];
+        assert_eq!(Day6::part2(&coords).unwrap(), 2 * 9999 * 10000 + 1);
+    }
+
+    #[test]
+    fn parallel_matches_sequential() {
+        let input = aoc_common::gen::generate::<Day6>(7, 50, &Default::default());
+        let cs = Day6::parse(&input).unwrap();
+        let bounds = grid_bounds(&cs).unwrap();
+        let grid = calc_grid(&cs, bounds, &find_closest_coord);
+        assert_eq!(
+            grid,
+            par::sequential(|| calc_grid(&cs, bounds, &find_closest_coord))
+        );
+        assert_eq!(
+            task_1(&cs).unwrap(),
+            par::sequential(|| task_1(&cs)).unwrap()
+        );
+    }
+
+    #[test]
+    fn generated_tiny() {
+        for size in 0..2 {
+            let input = aoc_common::gen::generate::<Day6>(7, size, &Default::default());
+            aoc_common::gen::check::<Day6>(&input).unwrap();
+        }
+    }
+
+    proptest! {
+        #[test]
+        fn coordinates_round_trip(x: i32, y: i32) {
+            let line = format!("{}, {}", x, y);
+            let c = Point::new(x as isize, y as isize);
+            prop_assert_eq!(parse_coordinate(&line), Ok(c));
+            prop_assert_eq!(parse_coordinate(&format!(" {} ,{} ", x, y)), Ok(c));
+        }
+    }
+
+    proptest! {
+        #![proptest_config(ProptestConfig::with_cases(8))]
+        #[test]
+        fn generated_matches_oracle(seed: u64, size in 40usize..80, spread in 10isize..60) {
+            let params = gen::Params { spread };
+            let input = aoc_common::gen::generate::<Day6>(seed, size, &params);
+            let checked = aoc_common::gen::check::<Day6>(&input);
+            prop_assert!(checked.is_ok(), "{}", checked.unwrap_err());
+        }
+    }
+
+    proptest! {
+        #[test]
+        fn safe_region_matches_brute_force(
+            points in prop::collection::vec((0isize..20, 0isize..20), 1..8),
+            threshold in 1isize..80,
+        ) {
+            let cs: Vec<Coordinate> = points.into_iter().map(Point::from).collect();
+            // no cell further than `threshold` from all coordinates is safe
+            let cells = Rect::bounding_box(cs.iter().copied()).unwrap().grow(threshold);
+            let brute_force = cells
+                .points()
+                .filter(|&cell| cs.iter().map(|&c| c.manhattan(cell)).sum::<isize>() < threshold)
+                .count();
+            prop_assert_eq!(safe_region_size(&cs, threshold), brute_force);
+        }
+    }
+}
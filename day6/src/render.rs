@@ -0,0 +1,107 @@
+//! Part 1 as a map of the areas closest to each coordinate, part 2 as the
+//! field of distance sums around the safe region.
+use aoc_common::render::{category, heat};
+use aoc_common::{grid, Image, Point, Rect, Render, Result, Rgb};
+
+use crate::{
+    area_sizes, calc_grid, find_closest_coord, grid_bounds, safe_margin, Coordinate, Day6,
+    MAX_TOTAL_DISTANCE,
+};
+
+/// Set the pixel for `cell` of the `shown` rectangle.
+fn set(image: &mut Image, shown: Rect, cell: Point, color: Rgb) {
+    let p = cell - shown.min();
+    image.set(p.x as usize, p.y as usize, color);
+}
+
+fn areas(cs: &[Coordinate]) -> Result<Image> {
+    let bounds = grid_bounds(cs)?;
+    let grid = calc_grid(cs, bounds, &find_closest_coord);
+    let sizes = area_sizes(cs.len(), &grid);
+    // infinite areas are drawn paler than the finite ones
+    let color = |c: usize| match sizes[c] {
+        Some(_) => category(c),
+        None => category(c).mix(Rgb::WHITE, 0.7),
+    };
+
+    let mut image = Image::new(bounds.width, bounds.height, Rgb::WHITE);
+    image.scale = 2;
+    image.title = "Day 6: areas closest to each coordinate".to_string();
+    for (cell, closest) in grid.iter() {
+        if let Some(c) = *closest {
+            set(&mut image, bounds, cell, color(c));
+        }
+    }
+    mark_coordinates(&mut image, cs, bounds);
+
+    let largest = (0..cs.len()).filter(|&c| sizes[c].is_some());
+    if let Some(largest) = largest.max_by_key(|&c| sizes[c]) {
+        let label = format!("largest finite area, {} cells", sizes[largest].unwrap());
+        image.add_legend(color(largest), label);
+    }
+    if let Some(infinite) = (0..cs.len()).find(|&c| sizes[c].is_none()) {
+        image.add_legend(color(infinite), "paler: infinite area");
+    }
+    image.add_legend(Rgb::WHITE, "as close to several");
+    image.add_legend(Rgb::BLACK, "coordinate");
+    Ok(image)
+}
+
+fn total_distance(cs: &[Coordinate], cell: Coordinate) -> isize {
+    cs.iter().map(|&c| c.manhattan(cell)).sum()
+}
+
+fn distance_sums(cs: &[Coordinate]) -> Result<Image> {
+    let bounds = grid_bounds(cs)?.grow(safe_margin(MAX_TOTAL_DISTANCE, cs.len()));
+    grid::check_size(bounds, "area around the safe region")?;
+    let sums = calc_grid(cs, bounds, &total_distance);
+    let least = sums.values().copied().min().unwrap();
+    // the smallest sums are darkest, the safe region fades out at its edge
+    let shade = |sum: isize| {
+        let t = (sum - least) as f64 / (MAX_TOTAL_DISTANCE - least).max(1) as f64;
+        heat(1.0 - 0.85 * t)
+    };
+
+    // only draw the coordinates and the safe region, with a little room
+    let safe: Vec<Coordinate> = sums
+        .iter()
+        .filter(|&(_, &sum)| sum < MAX_TOTAL_DISTANCE)
+        .map(|(cell, _)| cell)
+        .collect();
+    let room = 10;
+    let shown = Rect::bounding_box(cs.iter().chain(&safe).copied())
+        .unwrap()
+        .grow(room)
+        .intersection(&bounds)
+        .unwrap();
+
+    let mut image = Image::new(shown.width, shown.height, Rgb::GREY);
+    image.scale = 2;
+    image.title = format!("Day 6: safe region, {} cells", safe.len());
+    for &cell in &safe {
+        set(&mut image, shown, cell, shade(sums[cell]));
+    }
+    mark_coordinates(&mut image, cs, shown);
+
+    image.add_legend(shade(least), format!("distance sum {}", least));
+    let most = MAX_TOTAL_DISTANCE - 1;
+    image.add_legend(shade(most), format!("distance sum {}", most));
+    image.add_legend(Rgb::GREY, format!("{} or more", MAX_TOTAL_DISTANCE));
+    image.add_legend(Rgb::BLACK, "coordinate");
+    Ok(image)
+}
+
+fn mark_coordinates(image: &mut Image, cs: &[Coordinate], shown: Rect) {
+    for &c in cs {
+        set(image, shown, c, Rgb::BLACK);
+    }
+}
+
+impl Render for Day6 {
+    fn render(coords: &Vec<Coordinate>, part: u32) -> Result<Image> {
+        match part {
+            1 => areas(coords),
+            _ => distance_sums(coords),
+        }
+    }
+}
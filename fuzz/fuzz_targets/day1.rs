@@ -0,0 +1,5 @@
+#![no_main]
+use libfuzzer_sys::fuzz_target;
+
+// repeat1 stops after the passes that can still repeat
+fuzz_target!(|data: &[u8]| aoc2018_fuzz::run::<day1::Day1>(data, &["repeat1"]));
@@ -0,0 +1,40 @@
+//! Shared harness for the fuzz targets, one per day.
+use aoc_common::{Input, Solution};
+
+/// Parse `data` as a day's input and, if it parses, solve both parts and
+/// every variant. Errors are fine, but a variant has to agree with its part:
+/// a different answer, or an error where the part has an answer, is a bug.
+///
+/// `bounded` names the variants that may give up where the part does not,
+/// because they document a limit on the work they do.
+pub fn run<S: Solution>(data: &[u8], bounded: &[&str]) {
+    let input = Input::text("fuzz", String::from_utf8_lossy(data));
+    let parsed = match S::parse(&input) {
+        Ok(parsed) => parsed,
+        Err(_) => return,
+    };
+    let part1 = S::part1(&parsed).map(|a| a.to_string()).ok();
+    let part2 = S::part2(&parsed).map(|a| a.to_string()).ok();
+    for variant in S::VARIANTS {
+        let expected = match variant.part {
+            1 => &part1,
+            _ => &part2,
+        };
+        match ((variant.solve)(&input, &parsed), expected) {
+            (Ok(answer), Some(expected)) => assert_eq!(
+                &answer, expected,
+                "variant '{}' disagrees with part {}",
+                variant.name, variant.part
+            ),
+            (Ok(answer), None) => panic!(
+                "variant '{}' answered {} where part {} failed",
+                variant.name, answer, variant.part
+            ),
+            (Err(e), Some(_)) if !bounded.contains(&variant.name) => panic!(
+                "variant '{}' failed where part {} answered: {}",
+                variant.name, variant.part, e
+            ),
+            _ => (),
+        }
+    }
+}